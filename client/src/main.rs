@@ -62,6 +62,7 @@ pub fn init(key_file: &String) -> std::result::Result<(), state::Error> {
 
     let pda_seed: u32 = 6968193;
     let data_seed: u32 = 10399637;
+    let config_seed: u32 = 4812360;
 
     let (expected_pda_account, _pda_bump_seed) =
         Pubkey::find_program_address(&[&pda_seed.to_le_bytes()], &program);
@@ -69,6 +70,9 @@ pub fn init(key_file: &String) -> std::result::Result<(), state::Error> {
     let (expected_data_account, _data_bump_seed) =
         Pubkey::find_program_address(&[&data_seed.to_le_bytes()], &program);
 
+    let (expected_config_account, _config_bump_seed) =
+        Pubkey::find_program_address(&[&config_seed.to_le_bytes()], &program);
+
     let instruction = Instruction::new_with_borsh(
         program,
         &BlinkBashInstruction::Init,
@@ -76,6 +80,7 @@ pub fn init(key_file: &String) -> std::result::Result<(), state::Error> {
             AccountMeta::new_readonly(wallet.pubkey(), true),
             AccountMeta::new(expected_pda_account, false),
             AccountMeta::new(expected_data_account, false),
+            AccountMeta::new(expected_config_account, false),
             AccountMeta::new(token.pubkey(), true),
             AccountMeta::new(solana_sdk::system_program::id(), false),
             AccountMeta::new(spl_token_2022::id(), false),
@@ -5,8 +5,13 @@ use solana_program::{declare_id, pubkey::Pubkey};
 
 use spl_associated_token_account::get_associated_token_address_with_program_id;
 
+use crate::error::BlinkError;
+use crate::state;
+use borsh::BorshDeserialize;
+
 pub const PDA_SEED: u32 = 6968193;
 pub const DATA_SEED: u32 = 10399637;
+pub const CONFIG_SEED: u32 = 4812360;
 
 pub mod daoplays_account {
     use super::*;
@@ -43,7 +48,7 @@ pub mod core_account {
 pub fn check_core_key<'a>(account_info: &'a AccountInfo<'a>) -> ProgramResult {
     if account_info.key != &core_account::ID {
         msg!("expected core {} {}", core_account::ID, account_info.key);
-        return Err(ProgramError::InvalidAccountData);
+        return Err(BlinkError::InvalidProgramKey.into());
     }
 
     return Ok(());
@@ -56,7 +61,7 @@ pub fn check_wrapped_sol_key<'a>(account_info: &'a AccountInfo<'a>) -> ProgramRe
             wrapped_sol_mint_account::ID,
             account_info.key
         );
-        return Err(ProgramError::InvalidAccountData);
+        return Err(BlinkError::InvalidMint.into());
     }
 
     return Ok(());
@@ -68,7 +73,7 @@ pub fn check_system_program_key<'a>(account_info: &'a AccountInfo<'a>) -> Progra
             "expected system program {}",
             solana_program::system_program::ID
         );
-        return Err(ProgramError::InvalidAccountData);
+        return Err(BlinkError::InvalidProgramKey.into());
     }
 
     return Ok(());
@@ -85,7 +90,29 @@ pub fn check_token_program_key<'a>(
         return Ok(true);
     }
 
-    return Err(ProgramError::InvalidAccountData);
+    return Err(BlinkError::InvalidProgramKey.into());
+}
+
+// prizes and fees are only ever paid in $BASH through token 2022
+pub fn check_bash_accounts<'a>(
+    bash_mint: &'a AccountInfo<'a>,
+    token_2022: &'a AccountInfo<'a>,
+) -> ProgramResult {
+    if bash_mint.key != &bash_mint::ID {
+        msg!("expected bash mint {} {}", bash_mint::ID, bash_mint.key);
+        return Err(BlinkError::InvalidMint.into());
+    }
+
+    if token_2022.key != &spl_token_2022::ID {
+        msg!(
+            "expected token 2022 {} {}",
+            spl_token_2022::ID,
+            token_2022.key
+        );
+        return Err(BlinkError::InvalidProgramKey.into());
+    }
+
+    Ok(())
 }
 
 pub fn check_associated_token_program_key<'a>(account_info: &'a AccountInfo<'a>) -> ProgramResult {
@@ -95,7 +122,7 @@ pub fn check_associated_token_program_key<'a>(account_info: &'a AccountInfo<'a>)
             spl_associated_token_account::ID,
             account_info.key
         );
-        return Err(ProgramError::InvalidAccountData);
+        return Err(BlinkError::InvalidProgramKey.into());
     }
 
     return Ok(());
@@ -113,7 +140,7 @@ pub fn check_program_data_account<'a>(
         // the third account is the user's token account
         if account_info.key != &expected_data_account {
             msg!("expected program data account {}", expected_data_account);
-            return Err(ProgramError::InvalidAccountData);
+            return Err(BlinkError::InvalidPDA.into());
         }
 
         return Ok(bump_seed);
@@ -126,7 +153,7 @@ pub fn check_program_data_account<'a>(
         // the third account is the user's token account
         if account_info.key != &expected_data_account {
             msg!("expected program data account {}", expected_data_account);
-            return Err(ProgramError::InvalidAccountData);
+            return Err(BlinkError::InvalidPDA.into());
         }
 
         return Ok(bump_seed);
@@ -138,7 +165,7 @@ pub fn check_program_data_account<'a>(
     // the third account is the user's token account
     if account_info.key != &expected_data_account {
         msg!("expected program data account {}", expected_data_account);
-        return Err(ProgramError::InvalidAccountData);
+        return Err(BlinkError::InvalidPDA.into());
     }
 
     return Ok(bump_seed);
@@ -164,8 +191,84 @@ pub fn check_token_account<'a>(
             account_info.key,
             token_account_info.key
         );
-        return Err(ProgramError::InvalidAccountData);
+        return Err(BlinkError::InvalidTokenAccount.into());
     }
 
     return Ok(());
 }
+
+// fees are always paid to the prod fees account's associated token account
+pub fn check_fees_token_account<'a>(
+    mint_account_info: &'a AccountInfo<'a>,
+    token_account_info: &'a AccountInfo<'a>,
+    token_program: &'a AccountInfo<'a>,
+) -> ProgramResult {
+    let expected_token_account = get_associated_token_address_with_program_id(
+        &prod_fees_account::ID,
+        mint_account_info.key,
+        token_program.key,
+    );
+
+    if token_account_info.key != &expected_token_account {
+        msg!(
+            "expected fees token account {}, recieved {}",
+            expected_token_account,
+            token_account_info.key
+        );
+        return Err(BlinkError::InvalidTokenAccount.into());
+    }
+
+    Ok(())
+}
+
+pub fn check_not_paused<'a>(program_id: &Pubkey, accounts: &'a [AccountInfo<'a>]) -> ProgramResult {
+    let (expected_config_account, _bump_seed) =
+        Pubkey::find_program_address(&[&CONFIG_SEED.to_le_bytes()], program_id);
+
+    let config_account = match accounts.iter().find(|a| a.key == &expected_config_account) {
+        Some(config_account) => config_account,
+        None => {
+            msg!("expected config account {}", expected_config_account);
+            return Err(BlinkError::InvalidPDA.into());
+        }
+    };
+
+    let config = state::Config::try_from_slice(&config_account.data.borrow()[..])?;
+
+    if config.paused == 1 {
+        msg!("program is paused");
+        return Err(BlinkError::ProgramPaused.into());
+    }
+
+    Ok(())
+}
+
+pub fn check_listing_authority<'a>(
+    user_account_info: &'a AccountInfo<'a>,
+    listing: &state::Listing,
+    config: &state::Config,
+) -> ProgramResult {
+    if user_account_info.key != &listing.seller && user_account_info.key != &config.admin {
+        msg!(
+            "expected seller {} or admin {}, recieved {}",
+            listing.seller,
+            config.admin,
+            user_account_info.key
+        );
+        return Err(BlinkError::NotListingSeller.into());
+    }
+
+    Ok(())
+}
+
+pub fn check_admin<'a>(
+    user_account_info: &'a AccountInfo<'a>,
+    config: &state::Config,
+) -> ProgramResult {
+    if user_account_info.key != &config.admin {
+        msg!("expected admin {} {}", config.admin, user_account_info.key);
+        return Err(BlinkError::NotAdmin.into());
+    }
+
+    Ok(())
+}
@@ -1,8 +1,9 @@
 use solana_program::{
-    account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, pubkey::Pubkey,msg
+    account_info::AccountInfo, entrypoint, entrypoint::ProgramResult, msg,
+    program_error::PrintProgramError, pubkey::Pubkey,
 };
 
-use crate::processor::Processor;
+use crate::{error::BlinkError, processor::Processor};
 
 entrypoint!(process_instruction);
 fn process_instruction<'a>(
@@ -11,5 +12,10 @@ fn process_instruction<'a>(
     instruction_data: &[u8],
 ) -> ProgramResult {
     msg!("process");
-    Processor::process(program_id, accounts, instruction_data)
+    if let Err(error) = Processor::process(program_id, accounts, instruction_data) {
+        // catch the error so we can print it
+        error.print::<BlinkError>();
+        return Err(error);
+    }
+    Ok(())
 }
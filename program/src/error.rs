@@ -0,0 +1,192 @@
+use num_derive::FromPrimitive;
+use solana_program::{
+    decode_error::DecodeError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
+use thiserror::Error;
+
+#[derive(Error, Debug, Copy, Clone, FromPrimitive, PartialEq, Eq)]
+pub enum BlinkError {
+    #[error("Account is not the expected program derived address")]
+    InvalidPDA,
+
+    #[error("Token account is not the expected associated token account")]
+    InvalidTokenAccount,
+
+    #[error("Mint account is not the expected mint")]
+    InvalidMint,
+
+    #[error("Program account is not the expected program")]
+    InvalidProgramKey,
+
+    #[error("Creator cannot vote on their own entry")]
+    SelfVote,
+
+    #[error("No entry for date and user")]
+    EntryNotFound,
+
+    #[error("Vote must be 1 (positive) or 2 (negative)")]
+    InvalidVote,
+
+    #[error("User has already voted on this entry")]
+    AlreadyVoted,
+
+    #[error("User has not voted on this entry")]
+    VoteNotFound,
+
+    #[error("Vote is unchanged")]
+    VoteUnchanged,
+
+    #[error("Vote has already been retracted")]
+    VoteAlreadyRetracted,
+
+    #[error("Voting for the round has not closed")]
+    RoundNotOver,
+
+    #[error("Reward already claimed")]
+    RewardAlreadyClaimed,
+
+    #[error("No entrants in the leaderboard")]
+    EmptyLeaderboard,
+
+    #[error("User did not win a prize")]
+    NotAWinner,
+
+    #[error("Listing does not match the item")]
+    ListingMismatch,
+
+    #[error("Signer is not the program admin")]
+    NotAdmin,
+
+    #[error("Signer is not the pending admin")]
+    NotPendingAdmin,
+
+    #[error("Program is paused")]
+    ProgramPaused,
+
+    #[error("URI is too long")]
+    UriTooLong,
+
+    #[error("Twitter handle is too long")]
+    TwitterTooLong,
+
+    #[error("Display name is too long")]
+    DisplayNameTooLong,
+
+    #[error("Handle may only contain letters, numbers and underscores")]
+    InvalidHandle,
+
+    #[error("Handle is already claimed by another user")]
+    HandleTaken,
+
+    #[error("Required account was not provided")]
+    MissingAccount,
+
+    #[error("Leaderboard size must be between 1 and the maximum size")]
+    InvalidLeaderboardSize,
+
+    #[error("Prize table is longer than the leaderboard or shares exceed 100%")]
+    InvalidPrizeTable,
+
+    #[error("Game has not been registered")]
+    GameNotFound,
+
+    #[error("Game is already registered")]
+    GameAlreadyRegistered,
+
+    #[error("Game is not active")]
+    GameInactive,
+
+    #[error("Game has not started or has already ended")]
+    GameNotOpen,
+
+    #[error("Game name is too long")]
+    GameNameTooLong,
+
+    #[error("Game must end after it starts")]
+    InvalidGameWindow,
+
+    #[error("Round length must be non-zero and longer than the offset")]
+    InvalidRoundLength,
+
+    #[error("Submissions for this round are closed")]
+    SubmissionClosed,
+
+    #[error("Voting for this round is not open")]
+    VotingNotOpen,
+
+    #[error(
+        "Submission and voting phases must be non-zero and submissions must end within the round"
+    )]
+    InvalidPhases,
+
+    #[error("Leaderboard has not been finalized")]
+    LeaderboardNotFinalized,
+
+    #[error("Leaderboard has already been finalized")]
+    LeaderboardFinalized,
+
+    #[error("User accounts do not match the ranked entrants")]
+    WinnerMismatch,
+
+    #[error(
+        "Account cannot be closed until the prize is claimed or the retention period has passed"
+    )]
+    CannotClose,
+
+    #[error("Account is not the payer of the leaderboard")]
+    InvalidPayer,
+
+    #[error("Games with an entry fee need a pool prize table and a rake of at most 100%")]
+    InvalidEntryFee,
+
+    #[error("Signer is not the seller of this listing or the admin")]
+    NotListingSeller,
+
+    #[error("Seller account does not match the listing")]
+    SellerMismatch,
+
+    #[error("Payment must be 0 (burn) or 1 (seller)")]
+    InvalidPayment,
+
+    #[error("Market fee cannot exceed 100%")]
+    InvalidMarketFee,
+
+    #[error("Total price is above the buyer's maximum")]
+    SlippageExceeded,
+
+    #[error("Quantity must be a whole number of bundles")]
+    PartialBundle,
+
+    #[error("Round timing cannot change once the game has started")]
+    GameStarted,
+
+    #[error("Paused must be 0 or 1")]
+    InvalidPaused,
+}
+
+impl From<BlinkError> for ProgramError {
+    fn from(e: BlinkError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for BlinkError {
+    fn type_of() -> &'static str {
+        "Blink Error"
+    }
+}
+
+impl PrintProgramError for BlinkError {
+    fn print<E>(&self)
+    where
+        E: 'static
+            + std::error::Error
+            + DecodeError<E>
+            + PrintProgramError
+            + num_traits::FromPrimitive,
+    {
+        msg!(&self.to_string());
+    }
+}
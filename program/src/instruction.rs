@@ -1,5 +1,8 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use shank::{ShankContext, ShankInstruction};
+use solana_program::pubkey::Pubkey;
+
+use crate::state::PrizeTable;
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
 pub struct CreateMeta {
@@ -10,32 +13,103 @@ pub struct CreateMeta {
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
 pub struct EnterMeta {
     pub game: u8,
+    pub uri: String,
+    pub content_hash: [u8; 32],
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
 pub struct VoteMeta {
     pub game: u8,
+    // the round the entry was made in
+    pub date: u32,
     pub vote: u8,
 }
 
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct RetractVoteMeta {
+    pub game: u8,
+    pub date: u32,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
 pub struct ListMeta {
     pub item_type: u8,
+    pub payment: u8,
     pub quantity: u64,
+    // price is per bundle, 0 defaults to a single whole token
+    pub bundle_size: u64,
     pub price: u64,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
 pub struct PurchaseMeta {
     pub quantity: u64,
+    pub max_total_price: u64,
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
 pub struct ClaimPrizeMeta {
     pub game: u8,
+    // the round the entry was made in
     pub date: u32,
 }
 
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct RoundMeta {
+    pub game: u8,
+    pub date: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct UpdateConfigMeta {
+    pub entry_reward: u64,
+    pub entry_referral_reward: u64,
+    pub vote_reward: u64,
+    pub vote_referral_reward: u64,
+    pub max_rewarded_votes_per_day: u32,
+    pub retention_period: u32,
+    pub market_fee_bps: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct SetPausedMeta {
+    pub paused: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct TransferAdminMeta {
+    pub new_admin: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct SetProfileMeta {
+    pub twitter: String,
+    pub display_name: String,
+    pub avatar_uri: String,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct GameMeta {
+    pub game: u8,
+    pub name: String,
+    pub description_uri: String,
+    pub active: u8,
+    pub entry_reward: Option<u64>,
+    pub vote_reward: Option<u64>,
+    pub entry_fee: u64,
+    pub rake_bps: u16,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub round_length: u32,
+    pub round_offset: u32,
+    pub submission_length: u32,
+    pub voting_delay: u32,
+    pub voting_length: u32,
+    pub leaderboard_size: u8,
+    pub prizes: PrizeTable,
+    pub split_ties: u8,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
 pub struct Attribute {
     pub name: String,
@@ -50,79 +124,299 @@ pub enum BlinkInstruction {
     #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
     #[account(1, writable, name = "pda", desc = "pda account")]
     #[account(2, writable, name = "data", desc = "data account")]
-    #[account(3, writable, name = "token_mint", desc = "token mint account")]
-    #[account(4, name = "system_program", desc = "System program")]
-    #[account(5, name = "token_2022", desc = "token program")]
+    #[account(3, writable, name = "config", desc = "config account")]
+    #[account(4, writable, name = "token_mint", desc = "token mint account")]
+    #[account(5, name = "system_program", desc = "System program")]
+    #[account(6, name = "token_2022", desc = "token program")]
     Init(),
     #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
     #[account(1, writable, name = "pda", desc = "pda account")]
     #[account(2, writable, name = "data", desc = "data account")]
+    #[account(3, name = "config", desc = "config account")]
+    #[account(4, writable, name = "entry", desc = "entry account")]
+    #[account(5, writable, name = "user_data", desc = "user data account")]
+    #[account(6, writable, name = "bash_mint", desc = "user data account")]
+    #[account(7, writable, name = "user_token", desc = "user data account")]
+    #[account(8, writable, name = "leaderboard", desc = "leaderboard account")]
+    #[account(9, name = "system_program", desc = "System program")]
+    #[account(10, name = "token_2022", desc = "System program")]
+    #[account(11, name = "associated", desc = "System program")]
+    #[account(12, optional, name = "reference", desc = "ref user")]
+    #[account(13, optional, writable, name = "ref_bash", desc = "ref bash")]
+    #[account(14, name = "game", desc = "game account")]
+    #[account(15, optional, name = "pool", desc = "round prize pool")]
+    #[account(
+        16,
+        optional,
+        writable,
+        name = "pool_token",
+        desc = "round prize pool bash account"
+    )]
+    Enter(EnterMeta),
+    #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
+    #[account(1, writable, name = "pda", desc = "pda account")]
+    #[account(2, writable, name = "data", desc = "data account")]
+    #[account(3, name = "config", desc = "config account")]
+    #[account(4, writable, name = "entry", desc = "entry account")]
+    #[account(5, writable, name = "vote_record", desc = "vote record account")]
+    #[account(6, writable, name = "activity", desc = "user activity account")]
+    #[account(7, writable, name = "user_data", desc = "user data account")]
+    #[account(8, writable, name = "creator", desc = "creator account")]
+    #[account(9, writable, name = "creator_data", desc = "creator data account")]
+    #[account(10, writable, name = "leaderboard", desc = "leaderboard account")]
+    #[account(11, writable, name = "bash_mint", desc = "user data account")]
+    #[account(12, writable, name = "user_token", desc = "user data account")]
+    #[account(13, name = "system_program", desc = "System program")]
+    #[account(14, name = "token_2022", desc = "System program")]
+    #[account(15, name = "associated", desc = "System program")]
+    #[account(16, optional, name = "reference", desc = "ref user")]
+    #[account(17, optional, writable, name = "ref_bash", desc = "ref bash")]
+    #[account(18, name = "game", desc = "game account")]
+    Vote(VoteMeta),
+    #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
+    #[account(1, writable, name = "pda", desc = "pda account")]
+    #[account(2, name = "config", desc = "config account")]
     #[account(3, writable, name = "entry", desc = "entry account")]
     #[account(4, writable, name = "user_data", desc = "user data account")]
-    #[account(5, writable, name = "bash_mint", desc = "user data account")]
-    #[account(6, writable, name = "user_token", desc = "user data account")]
-    #[account(7, writable, name = "leaderboard", desc = "leaderboard account")]
+    #[account(5, writable, name = "leaderboard", desc = "leaderboard account")]
+    #[account(6, writable, name = "bash_mint", desc = "user data account")]
+    #[account(7, writable, name = "user_token", desc = "user data account")]
     #[account(8, name = "system_program", desc = "System program")]
     #[account(9, name = "token_2022", desc = "System program")]
     #[account(10, name = "associated", desc = "System program")]
-    #[account(11, optional, name = "reference", desc = "ref user")]
-    #[account(12, optional, writable, name = "ref_bash", desc = "ref bash")]
-    Enter(EnterMeta),
+    #[account(11, name = "game", desc = "game account")]
+    #[account(12, optional, name = "pool", desc = "round prize pool")]
+    #[account(
+        13,
+        optional,
+        writable,
+        name = "pool_token",
+        desc = "round prize pool bash account"
+    )]
+    ClaimPrize(ClaimPrizeMeta),
     #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
     #[account(1, writable, name = "pda", desc = "pda account")]
-    #[account(2, writable, name = "data", desc = "data account")]
-    #[account(3, writable, name = "entry", desc = "entry account")]
-    #[account(4, writable, name = "user_data", desc = "user data account")]
-    #[account(5, writable, name = "creator", desc = "creator account")]
-    #[account(6, writable, name = "creator_data", desc = "creator data account")]
-    #[account(7, writable, name = "leaderboard", desc = "leaderboard account")]
-    #[account(8, writable, name = "bash_mint", desc = "user data account")]
-    #[account(9, writable, name = "user_token", desc = "user data account")]
+    #[account(2, name = "config", desc = "config account")]
+    #[account(3, writable, name = "whitelist_mint", desc = "whitelist token")]
+    #[account(4, writable, name = "whitelist_account", desc = "whitelist token")]
+    #[account(5, writable, name = "item", desc = "item account")]
+    #[account(6, writable, name = "listing", desc = "item account")]
+    #[account(7, optional, writable, name = "pda_item", desc = "item account")]
+    #[account(8, optional, writable, name = "user_item", desc = "item account")]
+    #[account(9, optional, writable, name = "collection", desc = "item account")]
     #[account(10, name = "system_program", desc = "System program")]
-    #[account(11, name = "token_2022", desc = "System program")]
-    #[account(12, name = "associated", desc = "System program")]
-    #[account(13, optional, name = "reference", desc = "ref user")]
-    #[account(14, optional, writable, name = "ref_bash", desc = "ref bash")]
-    Vote(VoteMeta),
+    #[account(11, name = "core", desc = "Core program")]
+    #[account(12, name = "token_2022", desc = "Token 2022 program")]
+    #[account(13, name = "associated", desc = "Token 2022 program")]
+    #[account(14, name = "listing_tp", desc = "Token program for listing")]
+    ListItem(ListMeta),
     #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
     #[account(1, writable, name = "pda", desc = "pda account")]
-    #[account(2, writable, name = "entry", desc = "entry account")]
+    #[account(2, name = "config", desc = "config account")]
+    #[account(3, writable, name = "item", desc = "item account")]
+    #[account(4, writable, name = "listing", desc = "item account")]
+    #[account(5, writable, name = "pda_item", desc = "item account")]
+    #[account(6, writable, name = "user_item", desc = "item account")]
+    #[account(7, writable, name = "collection", desc = "item account")]
+    #[account(8, writable, name = "bash_mint", desc = "item account")]
+    #[account(9, writable, name = "user_bash", desc = "item account")]
+    #[account(10, name = "system_program", desc = "System program")]
+    #[account(11, name = "core", desc = "Core program")]
+    #[account(12, name = "token_2022", desc = "Token 2022 program")]
+    #[account(13, name = "associated", desc = "Token 2022 program")]
+    #[account(14, name = "listing_tp", desc = "Token program for listing")]
+    #[account(15, optional, writable, name = "seller", desc = "seller account")]
+    #[account(
+        16,
+        optional,
+        writable,
+        name = "seller_bash",
+        desc = "seller bash account"
+    )]
+    #[account(
+        17,
+        optional,
+        writable,
+        name = "fees_bash",
+        desc = "prod fees bash account"
+    )]
+    PurchaseItem(PurchaseMeta),
+    #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
+    #[account(1, writable, name = "entry", desc = "entry account")]
+    #[account(2, writable, name = "vote_record", desc = "vote record account")]
     #[account(3, writable, name = "user_data", desc = "user data account")]
-    #[account(4, writable, name = "leaderboard", desc = "leaderboard account")]
-    #[account(5, writable, name = "bash_mint", desc = "user data account")]
-    #[account(6, writable, name = "user_token", desc = "user data account")]
+    #[account(4, name = "creator", desc = "creator account")]
+    #[account(5, writable, name = "creator_data", desc = "creator data account")]
+    #[account(6, writable, name = "leaderboard", desc = "leaderboard account")]
     #[account(7, name = "system_program", desc = "System program")]
-    #[account(8, name = "token_2022", desc = "System program")]
-    #[account(9, name = "associated", desc = "System program")]
-    ClaimPrize(ClaimPrizeMeta),
+    #[account(8, name = "game", desc = "game account")]
+    ChangeVote(VoteMeta),
+    #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
+    #[account(1, writable, name = "entry", desc = "entry account")]
+    #[account(2, writable, name = "vote_record", desc = "vote record account")]
+    #[account(3, writable, name = "user_data", desc = "user data account")]
+    #[account(4, name = "creator", desc = "creator account")]
+    #[account(5, writable, name = "creator_data", desc = "creator data account")]
+    #[account(6, writable, name = "leaderboard", desc = "leaderboard account")]
+    #[account(7, name = "system_program", desc = "System program")]
+    #[account(8, name = "game", desc = "game account")]
+    RetractVote(RetractVoteMeta),
+    #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
+    #[account(1, writable, name = "config", desc = "config account")]
+    #[account(2, name = "system_program", desc = "System program")]
+    UpdateConfig(UpdateConfigMeta),
+    #[account(0, signer, name = "user", desc = "Users account, signer")]
+    #[account(1, writable, name = "config", desc = "config account")]
+    SetPaused(SetPausedMeta),
+    #[account(0, signer, name = "user", desc = "Users account, signer")]
+    #[account(1, writable, name = "config", desc = "config account")]
+    TransferAdmin(TransferAdminMeta),
+    #[account(0, signer, name = "user", desc = "Users account, signer")]
+    #[account(1, writable, name = "config", desc = "config account")]
+    AcceptAdmin(),
+    #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
+    #[account(1, writable, name = "data", desc = "data account")]
+    #[account(2, writable, name = "user_data", desc = "user data account")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(
+        4,
+        optional,
+        writable,
+        name = "handle",
+        desc = "handle account for the new twitter handle"
+    )]
+    #[account(
+        5,
+        optional,
+        writable,
+        name = "old_handle",
+        desc = "handle account for the current twitter handle"
+    )]
+    SetProfile(SetProfileMeta),
+    #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
+    #[account(1, name = "config", desc = "config account")]
+    #[account(2, writable, name = "game", desc = "game account")]
+    #[account(3, name = "system_program", desc = "System program")]
+    UpdateGame(GameMeta),
+    #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
+    #[account(1, name = "config", desc = "config account")]
+    #[account(2, writable, name = "game", desc = "game account")]
+    #[account(3, name = "system_program", desc = "System program")]
+    RegisterGame(GameMeta),
+    #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
+    #[account(1, writable, name = "leaderboard", desc = "leaderboard account")]
+    #[account(2, name = "game", desc = "game account")]
+    #[account(3, name = "system_program", desc = "System program")]
+    #[account(4, optional, name = "bash_mint", desc = "bash mint account")]
+    #[account(5, optional, name = "pool", desc = "round prize pool")]
+    #[account(
+        6,
+        optional,
+        writable,
+        name = "pool_token",
+        desc = "round prize pool bash account"
+    )]
+    #[account(
+        7,
+        optional,
+        writable,
+        name = "fees_token",
+        desc = "prod fees bash account"
+    )]
+    #[account(8, optional, name = "token_2022", desc = "Token 2022 program")]
+    FinalizeRound(RoundMeta),
     #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
     #[account(1, writable, name = "pda", desc = "pda account")]
-    #[account(2, writable, name = "whitelist_mint", desc = "whitelist token")]
-    #[account(3, writable, name = "whitelist_account", desc = "whitelist token")]
-    #[account(4, writable, name = "item", desc = "item account")]
-    #[account(5, writable, name = "listing", desc = "item account")]
-    #[account(6, optional, writable, name = "pda_item", desc = "item account")]
-    #[account(7, optional, writable, name = "user_item", desc = "item account")]
-    #[account(8, optional, writable, name = "collection", desc = "item account")]
-    #[account(9, name = "system_program", desc = "System program")]
-    #[account(10, name = "core", desc = "Core program")]
-    #[account(11, name = "token_2022", desc = "Token 2022 program")]
-    #[account(12, name = "associated", desc = "Token 2022 program")]
-    #[account(13, name = "listing_tp", desc = "Token program for listing")]
-    ListItem(ListMeta),
+    #[account(2, name = "config", desc = "config account")]
+    #[account(3, name = "leaderboard", desc = "leaderboard account")]
+    #[account(4, writable, name = "bash_mint", desc = "bash mint account")]
+    #[account(5, name = "system_program", desc = "System program")]
+    #[account(6, name = "token_2022", desc = "Token 2022 program")]
+    #[account(7, name = "associated", desc = "Associated token program")]
+    #[account(8, optional, name = "pool", desc = "round prize pool")]
+    #[account(
+        9,
+        optional,
+        writable,
+        name = "pool_token",
+        desc = "round prize pool bash account"
+    )]
+    DistributePrizes(RoundMeta),
+    #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
+    #[account(1, writable, name = "pda", desc = "pda account")]
+    #[account(2, name = "config", desc = "config account")]
+    #[account(3, writable, name = "bash_mint", desc = "bash mint account")]
+    #[account(4, writable, name = "user_token", desc = "user bash account")]
+    #[account(5, name = "system_program", desc = "System program")]
+    #[account(6, name = "token_2022", desc = "Token 2022 program")]
+    #[account(7, name = "associated", desc = "Associated token program")]
+    ClaimPrizes(Vec<ClaimPrizeMeta>),
+    #[account(0, signer, name = "user", desc = "Users account, signer")]
+    #[account(1, writable, name = "creator", desc = "creator account")]
+    #[account(2, writable, name = "entry", desc = "entry account")]
+    #[account(3, name = "config", desc = "config account")]
+    #[account(4, name = "game", desc = "game account")]
+    CloseEntry(RoundMeta),
+    #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
+    #[account(
+        1,
+        writable,
+        name = "payer",
+        desc = "account that paid for the leaderboard"
+    )]
+    #[account(2, writable, name = "leaderboard", desc = "leaderboard account")]
+    #[account(3, writable, name = "archive", desc = "round archive account")]
+    #[account(4, name = "config", desc = "config account")]
+    #[account(5, name = "game", desc = "game account")]
+    #[account(6, name = "system_program", desc = "System program")]
+    #[account(7, optional, name = "bash_mint", desc = "bash mint account")]
+    #[account(8, optional, name = "pool", desc = "round prize pool")]
+    #[account(
+        9,
+        optional,
+        writable,
+        name = "pool_token",
+        desc = "round prize pool bash account"
+    )]
+    #[account(
+        10,
+        optional,
+        writable,
+        name = "fees_token",
+        desc = "prod fees bash account"
+    )]
+    #[account(11, optional, name = "token_2022", desc = "Token 2022 program")]
+    CloseLeaderboard(RoundMeta),
     #[account(0, writable, signer, name = "user", desc = "Users account, signer")]
     #[account(1, writable, name = "pda", desc = "pda account")]
-    #[account(2, writable, name = "item", desc = "item account")]
-    #[account(3, writable, name = "listing", desc = "item account")]
-    #[account(4, writable, name = "pda_item", desc = "item account")]
-    #[account(5, writable, name = "user_item", desc = "item account")]
-    #[account(6, writable, name = "collection", desc = "item account")]
-    #[account(7, writable, name = "bash_mint", desc = "item account")]
-    #[account(8, writable, name = "user_bash", desc = "item account")]
+    #[account(2, name = "config", desc = "config account")]
+    #[account(3, writable, name = "item", desc = "item account")]
+    #[account(4, writable, name = "listing", desc = "listing account")]
+    #[account(5, writable, name = "seller", desc = "seller account")]
+    #[account(
+        6,
+        optional,
+        writable,
+        name = "pda_item",
+        desc = "pda item token account"
+    )]
+    #[account(
+        7,
+        optional,
+        writable,
+        name = "seller_item",
+        desc = "seller item token account"
+    )]
+    #[account(
+        8,
+        optional,
+        writable,
+        name = "collection",
+        desc = "core collection account"
+    )]
     #[account(9, name = "system_program", desc = "System program")]
     #[account(10, name = "core", desc = "Core program")]
-    #[account(11, name = "token_2022", desc = "Token 2022 program")]
-    #[account(12, name = "associated", desc = "Token 2022 program")]
-    #[account(13, name = "listing_tp", desc = "Token program for listing")]
-    PurchaseItem(PurchaseMeta),
+    #[account(11, name = "associated", desc = "Associated token program")]
+    #[account(12, name = "listing_tp", desc = "Token program for listing")]
+    DelistItem(),
 }
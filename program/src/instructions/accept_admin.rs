@@ -0,0 +1,39 @@
+use crate::error::BlinkError;
+use crate::instruction::accounts::AcceptAdminAccounts;
+use crate::{accounts, state};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::msg;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+pub fn accept_admin<'a>(program_id: &Pubkey, accounts: &'a [AccountInfo<'a>]) -> ProgramResult {
+    let ctx: crate::instruction::accounts::Context<AcceptAdminAccounts> =
+        AcceptAdminAccounts::context(accounts)?;
+
+    if !ctx.accounts.user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let _config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
+
+    let mut config = state::Config::try_from_slice(&ctx.accounts.config.data.borrow()[..])?;
+
+    if config.pending_admin == Pubkey::default() || ctx.accounts.user.key != &config.pending_admin {
+        msg!("expected pending admin {}", config.pending_admin);
+        return Err(BlinkError::NotPendingAdmin.into());
+    }
+
+    msg!("new admin {}", config.pending_admin);
+    config.admin = config.pending_admin;
+    config.pending_admin = Pubkey::default();
+
+    config.serialize(&mut &mut ctx.accounts.config.data.borrow_mut()[..])?;
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use crate::error::BlinkError;
+use crate::instruction::VoteMeta;
+use crate::instructions;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+pub fn change_vote<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: VoteMeta,
+) -> ProgramResult {
+    // retracting goes through RetractVote
+    if args.vote == 0 {
+        return Err(BlinkError::InvalidVote.into());
+    }
+
+    instructions::update_vote(program_id, accounts, args.game, args.date, args.vote)
+}
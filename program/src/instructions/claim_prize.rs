@@ -1,7 +1,7 @@
+use crate::error::BlinkError;
 use crate::instruction::accounts::ClaimPrizeAccounts;
 use crate::instruction::ClaimPrizeMeta;
-use crate::{accounts, state};
-use borsh::{BorshDeserialize, BorshSerialize};
+use crate::{accounts, utils};
 use solana_program::clock::Clock;
 use solana_program::msg;
 use solana_program::program::invoke_signed;
@@ -11,14 +11,6 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
-fn sort_users_by_scores(users: &Vec<u32>, scores: &Vec<u32>) -> Vec<u32> {
-    let mut indexed_users: Vec<(usize, &u32)> = users.iter().enumerate().collect();
-
-    indexed_users.sort_by(|&(i, _), &(j, _)| scores[j].cmp(&scores[i]));
-
-    indexed_users.into_iter().map(|(_, &user)| user).collect()
-}
-
 pub fn claim_prize<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
@@ -35,8 +27,13 @@ pub fn claim_prize<'a>(
         ctx.accounts.pda,
         program_id,
         vec![&accounts::PDA_SEED.to_le_bytes()],
-    )
-    .unwrap();
+    )?;
+
+    let _config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
 
     let _entry_bump_seed = accounts::check_program_data_account(
         ctx.accounts.entry,
@@ -46,8 +43,7 @@ pub fn claim_prize<'a>(
             &args.game.to_le_bytes(),
             &args.date.to_le_bytes(),
         ],
-    )
-    .unwrap();
+    )?;
 
     let _leaderboard_bump_seed = accounts::check_program_data_account(
         ctx.accounts.leaderboard,
@@ -57,91 +53,122 @@ pub fn claim_prize<'a>(
             &args.date.to_le_bytes(),
             b"Leaderboard",
         ],
-    )
-    .unwrap();
+    )?;
 
     let _user_data_bump = accounts::check_program_data_account(
         ctx.accounts.user_data,
         program_id,
         vec![&ctx.accounts.user.key.to_bytes(), b"User"],
-    )
-    .unwrap();
+    )?;
 
     accounts::check_system_program_key(ctx.accounts.system_program)?;
 
+    // prizes for a game that has since ended can still be claimed
+    let game = utils::load_game(ctx.accounts.game, program_id, args.game)?;
+
     let clock = Clock::get()?;
-    let current_date = (clock.unix_timestamp / (24 * 60 * 60)) as u32;
+    let voting_end = utils::get_voting_end(&game, args.date);
 
-    if args.date == current_date {
-        msg!("cannot claim prize on the same day as the game");
-        return Err(ProgramError::InvalidAccountData);
+    if clock.unix_timestamp < voting_end {
+        msg!("cannot claim prize before voting closes at {}", voting_end);
+        return Err(BlinkError::RoundNotOver.into());
     }
 
-    let mut entry = state::Entry::try_from_slice(&ctx.accounts.entry.data.borrow()[..])?;
-    let mut user_data = state::User::try_from_slice(&ctx.accounts.user_data.data.borrow()[..])?;
-    let leaderboard =
-        state::Leaderboard::try_from_slice(&ctx.accounts.leaderboard.data.borrow()[..])?;
+    let mut entry = utils::read_entry(ctx.accounts.entry)?;
+    let leaderboard = utils::read_leaderboard(ctx.accounts.leaderboard)?;
+
+    if leaderboard.finalized == 0 {
+        msg!("round must be finalized before prizes can be claimed");
+        return Err(BlinkError::LeaderboardNotFinalized.into());
+    }
 
     if entry.reward_claimed == 1 {
         msg!("reward already claimed");
-        return Err(ProgramError::InvalidAccountData);
+        return Err(BlinkError::RewardAlreadyClaimed.into());
     }
 
     let n_players = leaderboard.entrants.len();
     if n_players == 0 {
         msg!("no entrants in the leaderboard");
-        return Err(ProgramError::InvalidAccountData);
+        return Err(BlinkError::EmptyLeaderboard.into());
     }
 
-    let sorted_users = sort_users_by_scores(&leaderboard.entrants, &leaderboard.scores);
-    msg!("have users sorted by scores: {:?}", sorted_users);
-    let amount: u64 = if sorted_users[0] == user_data.user_id {
-        5000
-    } else if n_players >= 2 && sorted_users[1] == user_data.user_id {
-        2500
-    } else if n_players >= 3 && sorted_users[2] == user_data.user_id {
-        1000
-    } else {
-        0
+    let amount: u64 = match leaderboard
+        .winners
+        .iter()
+        .position(|winner| winner == ctx.accounts.user.key)
+    {
+        Some(rank) => leaderboard.prizes[rank],
+        None => 0,
     };
 
     if amount == 0 {
         msg!("user did not win a prize");
-        return Err(ProgramError::InvalidAccountData);
+        return Err(BlinkError::NotAWinner.into());
     }
 
-    if amount == 5000 {
-        user_data.total_wins += 1;
-        user_data.serialize(&mut &mut ctx.accounts.user_data.data.borrow_mut()[..])?;
+    // fee games pay prizes out of the round's pool rather than minting them
+    if leaderboard.pool > 0 {
+        let (pool, pool_token) =
+            utils::unwrap_pool_accounts(ctx.accounts.pool, ctx.accounts.pool_token)?;
+
+        let pool_bump_seed = utils::check_pool(
+            program_id,
+            args.game,
+            args.date,
+            pool,
+            pool_token,
+            ctx.accounts.bash_mint,
+            ctx.accounts.token_2022,
+        )?;
+
+        accounts::check_token_account(
+            ctx.accounts.user,
+            ctx.accounts.bash_mint,
+            ctx.accounts.user_token,
+            ctx.accounts.token_2022,
+        )?;
+
+        utils::pay_from_pool(
+            amount,
+            args.game,
+            args.date,
+            pool,
+            pool_token,
+            ctx.accounts.bash_mint,
+            ctx.accounts.user_token,
+            ctx.accounts.token_2022,
+            pool_bump_seed,
+        )?;
+    } else {
+        // mint the token to the user
+        let mint_to_idx = spl_token_2022::instruction::mint_to_checked(
+            ctx.accounts.token_2022.key,
+            ctx.accounts.bash_mint.key,
+            ctx.accounts.user_token.key,
+            ctx.accounts.pda.key,
+            &[ctx.accounts.pda.key],
+            amount,
+            1,
+        )
+        .unwrap();
+
+        invoke_signed(
+            &mint_to_idx,
+            &[
+                ctx.accounts.token_2022.clone(),
+                ctx.accounts.bash_mint.clone(),
+                ctx.accounts.user_token.clone(),
+                ctx.accounts.user.clone(),
+                ctx.accounts.pda.clone(),
+            ],
+            &[&[&accounts::PDA_SEED.to_le_bytes(), &[pda_bump_seed]]],
+        )?;
     }
 
-    // mint the token to the user
-    let mint_to_idx = spl_token_2022::instruction::mint_to_checked(
-        ctx.accounts.token_2022.key,
-        ctx.accounts.bash_mint.key,
-        ctx.accounts.user_token.key,
-        ctx.accounts.pda.key,
-        &[ctx.accounts.pda.key],
-        amount,
-        1,
-    )
-    .unwrap();
-
-    invoke_signed(
-        &mint_to_idx,
-        &[
-            ctx.accounts.token_2022.clone(),
-            ctx.accounts.bash_mint.clone(),
-            ctx.accounts.user_token.clone(),
-            ctx.accounts.user.clone(),
-            ctx.accounts.pda.clone(),
-        ],
-        &[&[&accounts::PDA_SEED.to_le_bytes(), &[pda_bump_seed]]],
-    )?;
-
     entry.reward_claimed = 1;
 
-    entry.serialize(&mut &mut ctx.accounts.entry.data.borrow_mut()[..])?;
+    utils::write_entry(ctx.accounts.entry, &entry)?;
 
     Ok(())
 }
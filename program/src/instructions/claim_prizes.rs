@@ -0,0 +1,194 @@
+use crate::error::BlinkError;
+use crate::instruction::accounts::ClaimPrizesAccounts;
+use crate::instruction::ClaimPrizeMeta;
+use crate::{accounts, utils};
+use solana_program::msg;
+use solana_program::program::invoke_signed;
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+// claims the prizes for several finalized rounds at once.  the entry, leaderboard and game for
+// each (game, date) are passed as remaining accounts in the same order as the list, followed by
+// the pool and pool token accounts for rounds of games with an entry fee
+pub fn claim_prizes<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: Vec<ClaimPrizeMeta>,
+) -> ProgramResult {
+    let ctx: crate::instruction::accounts::Context<ClaimPrizesAccounts> =
+        ClaimPrizesAccounts::context(accounts)?;
+
+    if !ctx.accounts.user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let pda_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.pda,
+        program_id,
+        vec![&accounts::PDA_SEED.to_le_bytes()],
+    )?;
+
+    let _config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
+
+    accounts::check_token_account(
+        ctx.accounts.user,
+        ctx.accounts.bash_mint,
+        ctx.accounts.user_token,
+        ctx.accounts.token_2022,
+    )?;
+
+    accounts::check_system_program_key(ctx.accounts.system_program)?;
+
+    utils::create_ata(
+        ctx.accounts.user,
+        ctx.accounts.user,
+        ctx.accounts.bash_mint,
+        ctx.accounts.user_token,
+        ctx.accounts.token_2022,
+    )?;
+
+    // minted prizes are added up and paid at the end, prizes from fee games are paid straight
+    // out of each round's pool
+    let mut total: u64 = 0;
+    let remaining = &mut ctx.remaining_accounts.iter();
+
+    for claim in args.iter() {
+        let entry_account = next_account_info(remaining)?;
+        let leaderboard_account = next_account_info(remaining)?;
+        let game_account = next_account_info(remaining)?;
+
+        let _entry_bump_seed = accounts::check_program_data_account(
+            entry_account,
+            program_id,
+            vec![
+                &ctx.accounts.user.key.to_bytes(),
+                &claim.game.to_le_bytes(),
+                &claim.date.to_le_bytes(),
+            ],
+        )?;
+
+        let _leaderboard_bump_seed = accounts::check_program_data_account(
+            leaderboard_account,
+            program_id,
+            vec![
+                &claim.game.to_le_bytes(),
+                &claim.date.to_le_bytes(),
+                b"Leaderboard",
+            ],
+        )?;
+
+        // the same game checks as ClaimPrize, so deactivated games can't be claimed in a batch
+        let _game = utils::load_game(game_account, program_id, claim.game)?;
+
+        let mut entry = utils::read_entry(entry_account)?;
+        let leaderboard = utils::read_leaderboard(leaderboard_account)?;
+
+        if leaderboard.finalized == 0 {
+            msg!(
+                "round {} of game {} is not finalized",
+                claim.date,
+                claim.game
+            );
+            return Err(BlinkError::LeaderboardNotFinalized.into());
+        }
+
+        if entry.reward_claimed == 1 {
+            msg!(
+                "reward already claimed for round {} of game {}",
+                claim.date,
+                claim.game
+            );
+            return Err(BlinkError::RewardAlreadyClaimed.into());
+        }
+
+        let amount: u64 = match leaderboard
+            .winners
+            .iter()
+            .position(|winner| winner == ctx.accounts.user.key)
+        {
+            Some(rank) => leaderboard.prizes[rank],
+            None => 0,
+        };
+
+        if amount == 0 {
+            msg!(
+                "user did not win a prize in round {} of game {}",
+                claim.date,
+                claim.game
+            );
+            return Err(BlinkError::NotAWinner.into());
+        }
+
+        if leaderboard.pool > 0 {
+            let pool = next_account_info(remaining)?;
+            let pool_token = next_account_info(remaining)?;
+
+            let pool_bump_seed = utils::check_pool(
+                program_id,
+                claim.game,
+                claim.date,
+                pool,
+                pool_token,
+                ctx.accounts.bash_mint,
+                ctx.accounts.token_2022,
+            )?;
+
+            utils::pay_from_pool(
+                amount,
+                claim.game,
+                claim.date,
+                pool,
+                pool_token,
+                ctx.accounts.bash_mint,
+                ctx.accounts.user_token,
+                ctx.accounts.token_2022,
+                pool_bump_seed,
+            )?;
+        } else {
+            total = total
+                .checked_add(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+
+        entry.reward_claimed = 1;
+        utils::write_entry(entry_account, &entry)?;
+    }
+
+    if total == 0 {
+        return Ok(());
+    }
+
+    // mint the combined prizes to the user
+    let mint_to_idx = spl_token_2022::instruction::mint_to_checked(
+        ctx.accounts.token_2022.key,
+        ctx.accounts.bash_mint.key,
+        ctx.accounts.user_token.key,
+        ctx.accounts.pda.key,
+        &[ctx.accounts.pda.key],
+        total,
+        1,
+    )
+    .unwrap();
+
+    invoke_signed(
+        &mint_to_idx,
+        &[
+            ctx.accounts.token_2022.clone(),
+            ctx.accounts.bash_mint.clone(),
+            ctx.accounts.user_token.clone(),
+            ctx.accounts.user.clone(),
+            ctx.accounts.pda.clone(),
+        ],
+        &[&[&accounts::PDA_SEED.to_le_bytes(), &[pda_bump_seed]]],
+    )?;
+
+    Ok(())
+}
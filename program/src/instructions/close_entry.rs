@@ -0,0 +1,95 @@
+use crate::error::BlinkError;
+use crate::instruction::accounts::CloseEntryAccounts;
+use crate::instruction::RoundMeta;
+use crate::{accounts, state, utils};
+use borsh::BorshDeserialize;
+use solana_program::clock::Clock;
+use solana_program::msg;
+use solana_program::sysvar::Sysvar;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+// anyone can close an entry once its prize has been claimed or the retention period after
+// voting has passed, the rent always goes back to the creator.  vote records on the entry can be
+// passed as remaining accounts in pairs of (voter, vote record) to return their rent to the
+// voters, either alongside the entry or after it has been closed
+pub fn close_entry<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: RoundMeta,
+) -> ProgramResult {
+    let ctx: crate::instruction::accounts::Context<CloseEntryAccounts> =
+        CloseEntryAccounts::context(accounts)?;
+
+    if !ctx.accounts.user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let _entry_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.entry,
+        program_id,
+        vec![
+            &ctx.accounts.creator.key.to_bytes(),
+            &args.game.to_le_bytes(),
+            &args.date.to_le_bytes(),
+        ],
+    )?;
+
+    let _config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
+
+    let votes = ctx.remaining_accounts.chunks_exact(2);
+
+    if !votes.remainder().is_empty() {
+        msg!("vote records must be passed as voter and vote record");
+        return Err(BlinkError::MissingAccount.into());
+    }
+
+    if ctx.accounts.entry.owner == program_id {
+        let config = state::Config::try_from_slice(&ctx.accounts.config.data.borrow()[..])?;
+        let game = utils::read_game(ctx.accounts.game, program_id, args.game)?;
+        let entry = utils::read_entry(ctx.accounts.entry)?;
+
+        let clock = Clock::get()?;
+        let expires = utils::get_voting_end(&game, args.date) + config.retention_period as i64;
+
+        if entry.reward_claimed == 0 && clock.unix_timestamp < expires {
+            msg!("entry can be closed once claimed or after {}", expires);
+            return Err(BlinkError::CannotClose.into());
+        }
+
+        utils::close_program_account(ctx.accounts.entry, ctx.accounts.creator)?;
+    } else if votes.len() == 0 {
+        msg!("No entry for date and user");
+        return Err(BlinkError::EntryNotFound.into());
+    }
+
+    // votes can't be cast or changed once the entry is gone, so the records are no longer needed
+    for vote in votes {
+        let (voter, vote_record) = (&vote[0], &vote[1]);
+
+        let _vote_record_bump_seed = accounts::check_program_data_account(
+            vote_record,
+            program_id,
+            vec![
+                &voter.key.to_bytes(),
+                &ctx.accounts.entry.key.to_bytes(),
+                b"Vote",
+            ],
+        )?;
+
+        if vote_record.owner != program_id {
+            msg!("No vote recorded by {}", voter.key);
+            return Err(BlinkError::VoteNotFound.into());
+        }
+
+        utils::close_program_account(vote_record, voter)?;
+    }
+
+    Ok(())
+}
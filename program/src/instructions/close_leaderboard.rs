@@ -0,0 +1,175 @@
+use crate::error::BlinkError;
+use crate::instruction::accounts::CloseLeaderboardAccounts;
+use crate::instruction::RoundMeta;
+use crate::state::Archive;
+use crate::{accounts, state, utils};
+use borsh::{to_vec, BorshDeserialize, BorshSerialize};
+use solana_program::clock::Clock;
+use solana_program::msg;
+use solana_program::rent;
+use solana_program::sysvar::Sysvar;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+// anyone can close a finalized leaderboard once the retention period has passed.  the prize
+// winners are moved to an archive account for the round, whose rent is refunded to the caller
+// from the leaderboard's own, and whatever is left goes back to the account that paid for the
+// leaderboard.  unclaimed prizes left in a fee round's pool are swept to the fees account
+pub fn close_leaderboard<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: RoundMeta,
+) -> ProgramResult {
+    let ctx: crate::instruction::accounts::Context<CloseLeaderboardAccounts> =
+        CloseLeaderboardAccounts::context(accounts)?;
+
+    if !ctx.accounts.user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let _leaderboard_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.leaderboard,
+        program_id,
+        vec![
+            &args.game.to_le_bytes(),
+            &args.date.to_le_bytes(),
+            b"Leaderboard",
+        ],
+    )?;
+
+    let archive_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.archive,
+        program_id,
+        vec![
+            &args.game.to_le_bytes(),
+            &args.date.to_le_bytes(),
+            b"Archive",
+        ],
+    )?;
+
+    let _config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
+
+    accounts::check_system_program_key(ctx.accounts.system_program)?;
+
+    let config = state::Config::try_from_slice(&ctx.accounts.config.data.borrow()[..])?;
+    let game = utils::read_game(ctx.accounts.game, program_id, args.game)?;
+    let leaderboard = utils::read_leaderboard(ctx.accounts.leaderboard)?;
+
+    let payer = if leaderboard.payer == Pubkey::default() {
+        config.admin
+    } else {
+        leaderboard.payer
+    };
+
+    if ctx.accounts.payer.key != &payer {
+        msg!("expected payer {}", payer);
+        return Err(BlinkError::InvalidPayer.into());
+    }
+
+    if leaderboard.finalized == 0 {
+        msg!("round must be finalized before the leaderboard can be closed");
+        return Err(BlinkError::LeaderboardNotFinalized.into());
+    }
+
+    let clock = Clock::get()?;
+    let expires = utils::get_voting_end(&game, args.date) + config.retention_period as i64;
+
+    if clock.unix_timestamp < expires {
+        msg!("leaderboard can be closed after {}", expires);
+        return Err(BlinkError::CannotClose.into());
+    }
+
+    if leaderboard.pool > 0 {
+        let (bash_mint, pool, pool_token, fees_token, token_2022) = utils::unwrap_fee_accounts(
+            ctx.accounts.bash_mint,
+            ctx.accounts.pool,
+            ctx.accounts.pool_token,
+            ctx.accounts.fees_token,
+            ctx.accounts.token_2022,
+        )?;
+
+        accounts::check_bash_accounts(bash_mint, token_2022)?;
+
+        let pool_bump_seed = utils::check_pool(
+            program_id, args.game, args.date, pool, pool_token, bash_mint, token_2022,
+        )?;
+
+        accounts::check_fees_token_account(bash_mint, fees_token, token_2022)?;
+
+        if **pool_token.try_borrow_lamports()? > 0 {
+            let unclaimed = utils::get_token_balance(pool_token);
+
+            if unclaimed > 0 {
+                utils::pay_from_pool(
+                    unclaimed,
+                    args.game,
+                    args.date,
+                    pool,
+                    pool_token,
+                    bash_mint,
+                    fees_token,
+                    token_2022,
+                    pool_bump_seed,
+                )?;
+            }
+
+            utils::close_pool(
+                args.game,
+                args.date,
+                pool,
+                pool_token,
+                ctx.accounts.payer,
+                token_2022,
+                pool_bump_seed,
+            )?;
+        }
+    }
+
+    let mut archive = Archive {
+        account_type: state::AccountType::Archive,
+        game: args.game,
+        date: args.date,
+        winners: Vec::new(),
+        prizes: Vec::new(),
+    };
+
+    for (winner, &prize) in leaderboard.winners.iter().zip(leaderboard.prizes.iter()) {
+        if prize > 0 {
+            archive.winners.push(*winner);
+            archive.prizes.push(prize);
+        }
+    }
+
+    let archive_size = to_vec(&archive).unwrap().len();
+
+    utils::create_program_account(
+        ctx.accounts.user,
+        ctx.accounts.archive,
+        program_id,
+        archive_bump_seed,
+        archive_size,
+        vec![
+            &args.game.to_le_bytes(),
+            &args.date.to_le_bytes(),
+            b"Archive",
+        ],
+    )?;
+
+    archive.serialize(&mut &mut ctx.accounts.archive.data.borrow_mut()[..])?;
+
+    // the archive is always smaller than the leaderboard, so its rent covers the refund
+    let archive_rent = rent::Rent::default().minimum_balance(archive_size);
+
+    **ctx.accounts.leaderboard.try_borrow_mut_lamports()? -= archive_rent;
+    **ctx.accounts.user.try_borrow_mut_lamports()? += archive_rent;
+
+    utils::close_program_account(ctx.accounts.leaderboard, ctx.accounts.payer)?;
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use crate::error::BlinkError;
+use crate::instruction::accounts::DelistItemAccounts;
+use crate::{accounts, state, utils};
+use borsh::BorshDeserialize;
+use mpl_core::instructions::TransferV1CpiBuilder;
+use solana_program::msg;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use spl_token_2022::extension::StateWithExtensions;
+
+// returns any unsold stock held by the pda to the seller and closes the listing
+pub fn delist_item<'a>(program_id: &Pubkey, accounts: &'a [AccountInfo<'a>]) -> ProgramResult {
+    let ctx: crate::instruction::accounts::Context<DelistItemAccounts> =
+        DelistItemAccounts::context(accounts)?;
+
+    if !ctx.accounts.user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let pda_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.pda,
+        program_id,
+        vec![&accounts::PDA_SEED.to_le_bytes()],
+    )?;
+
+    let _config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
+
+    let _listing_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.listing,
+        program_id,
+        vec![&ctx.accounts.item.key.to_bytes(), b"Listing"],
+    )?;
+
+    let listing_2022 = accounts::check_token_program_key(ctx.accounts.listing_tp)?;
+
+    accounts::check_system_program_key(ctx.accounts.system_program)?;
+    accounts::check_core_key(ctx.accounts.core)?;
+    accounts::check_associated_token_program_key(ctx.accounts.associated)?;
+
+    let config = state::Config::try_from_slice(&ctx.accounts.config.data.borrow()[..])?;
+
+    let listing = utils::read_listing(ctx.accounts.listing, &config)?;
+
+    if listing.item_address != *ctx.accounts.item.key {
+        return Err(BlinkError::ListingMismatch.into());
+    }
+
+    accounts::check_listing_authority(ctx.accounts.user, &listing, &config)?;
+
+    if ctx.accounts.seller.key != &listing.seller {
+        msg!("expected seller {}", listing.seller);
+        return Err(BlinkError::SellerMismatch.into());
+    }
+
+    msg!("returning {} of {}", listing.quantity, listing.item_address);
+
+    //token
+    if listing.item_type == 1 && listing.quantity > 0 {
+        let (pda_item, seller_item) = match (ctx.accounts.pda_item, ctx.accounts.seller_item) {
+            (Some(pda_item), Some(seller_item)) => (pda_item, seller_item),
+            _ => return Err(BlinkError::MissingAccount.into()),
+        };
+
+        let mint_data = ctx.accounts.item.data.borrow();
+        let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+
+        accounts::check_token_account(
+            ctx.accounts.pda,
+            ctx.accounts.item,
+            pda_item,
+            ctx.accounts.listing_tp,
+        )?;
+
+        accounts::check_token_account(
+            ctx.accounts.seller,
+            ctx.accounts.item,
+            seller_item,
+            ctx.accounts.listing_tp,
+        )?;
+
+        utils::create_ata(
+            ctx.accounts.user,
+            ctx.accounts.seller,
+            ctx.accounts.item,
+            seller_item,
+            ctx.accounts.listing_tp,
+        )?;
+
+        utils::transfer_tokens(
+            listing_2022,
+            listing.quantity,
+            pda_item,
+            ctx.accounts.item,
+            seller_item,
+            ctx.accounts.pda,
+            ctx.accounts.listing_tp,
+            pda_bump_seed,
+            &vec![&accounts::PDA_SEED.to_le_bytes()],
+            mint.base.decimals,
+            &Vec::new(),
+        )?;
+    }
+
+    //core asset
+    if listing.item_type == 2 && listing.quantity > 0 {
+        let collection = match ctx.accounts.collection {
+            Some(collection) => collection,
+            None => return Err(BlinkError::MissingAccount.into()),
+        };
+
+        TransferV1CpiBuilder::new(ctx.accounts.core)
+            .asset(ctx.accounts.item)
+            .authority(Some(ctx.accounts.pda))
+            .payer(ctx.accounts.user)
+            .new_owner(ctx.accounts.seller)
+            .collection(Some(collection))
+            .invoke_signed(&[&[&accounts::PDA_SEED.to_le_bytes(), &[pda_bump_seed]]])?;
+    }
+
+    // the seller paid the rent for the listing so they get it back
+    utils::close_program_account(ctx.accounts.listing, ctx.accounts.seller)?;
+
+    Ok(())
+}
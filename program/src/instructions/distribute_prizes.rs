@@ -0,0 +1,158 @@
+use crate::error::BlinkError;
+use crate::instruction::accounts::DistributePrizesAccounts;
+use crate::instruction::RoundMeta;
+use crate::{accounts, utils};
+use solana_program::msg;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+// anyone can pay out the prizes for a finalized round.  winners are passed as remaining
+// accounts in groups of (wallet, entry, bash token account), and any that have already been
+// paid are skipped so large leaderboards can be split over several transactions
+pub fn distribute_prizes<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: RoundMeta,
+) -> ProgramResult {
+    let ctx: crate::instruction::accounts::Context<DistributePrizesAccounts> =
+        DistributePrizesAccounts::context(accounts)?;
+
+    if !ctx.accounts.user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let pda_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.pda,
+        program_id,
+        vec![&accounts::PDA_SEED.to_le_bytes()],
+    )?;
+
+    let _config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
+
+    let _leaderboard_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.leaderboard,
+        program_id,
+        vec![
+            &args.game.to_le_bytes(),
+            &args.date.to_le_bytes(),
+            b"Leaderboard",
+        ],
+    )?;
+
+    accounts::check_system_program_key(ctx.accounts.system_program)?;
+    accounts::check_bash_accounts(ctx.accounts.bash_mint, ctx.accounts.token_2022)?;
+    accounts::check_associated_token_program_key(ctx.accounts.associated)?;
+
+    let leaderboard = utils::read_leaderboard(ctx.accounts.leaderboard)?;
+
+    if leaderboard.finalized == 0 {
+        msg!("round must be finalized before prizes can be distributed");
+        return Err(BlinkError::LeaderboardNotFinalized.into());
+    }
+
+    // fee games pay prizes out of the round's pool rather than minting them
+    let pool_accounts = if leaderboard.pool > 0 {
+        let (pool, pool_token) =
+            utils::unwrap_pool_accounts(ctx.accounts.pool, ctx.accounts.pool_token)?;
+
+        let pool_bump_seed = utils::check_pool(
+            program_id,
+            args.game,
+            args.date,
+            pool,
+            pool_token,
+            ctx.accounts.bash_mint,
+            ctx.accounts.token_2022,
+        )?;
+
+        Some((pool, pool_token, pool_bump_seed))
+    } else {
+        None
+    };
+
+    let winners = ctx.remaining_accounts.chunks_exact(3);
+
+    if !winners.remainder().is_empty() {
+        msg!("winners must be passed as wallet, entry and token account");
+        return Err(BlinkError::MissingAccount.into());
+    }
+
+    for winner in winners {
+        let (wallet, entry_account, token_account) = (&winner[0], &winner[1], &winner[2]);
+
+        let amount: u64 = match leaderboard.winners.iter().position(|w| w == wallet.key) {
+            Some(rank) => leaderboard.prizes[rank],
+            None => 0,
+        };
+
+        if amount == 0 {
+            msg!("{} did not win a prize", wallet.key);
+            return Err(BlinkError::NotAWinner.into());
+        }
+
+        let _entry_bump_seed = accounts::check_program_data_account(
+            entry_account,
+            program_id,
+            vec![
+                &wallet.key.to_bytes(),
+                &args.game.to_le_bytes(),
+                &args.date.to_le_bytes(),
+            ],
+        )?;
+
+        accounts::check_token_account(
+            wallet,
+            ctx.accounts.bash_mint,
+            token_account,
+            ctx.accounts.token_2022,
+        )?;
+
+        let mut entry = utils::read_entry(entry_account)?;
+
+        if entry.reward_claimed == 1 {
+            msg!("prize already paid to {}", wallet.key);
+            continue;
+        }
+
+        utils::create_ata(
+            ctx.accounts.user,
+            wallet,
+            ctx.accounts.bash_mint,
+            token_account,
+            ctx.accounts.token_2022,
+        )?;
+
+        match pool_accounts {
+            Some((pool, pool_token, pool_bump_seed)) => utils::pay_from_pool(
+                amount,
+                args.game,
+                args.date,
+                pool,
+                pool_token,
+                ctx.accounts.bash_mint,
+                token_account,
+                ctx.accounts.token_2022,
+                pool_bump_seed,
+            )?,
+            None => utils::mint(
+                amount,
+                ctx.accounts.token_2022,
+                ctx.accounts.bash_mint,
+                token_account,
+                ctx.accounts.pda,
+                pda_bump_seed,
+            )?,
+        }
+
+        entry.reward_claimed = 1;
+        utils::write_entry(entry_account, &entry)?;
+    }
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+use crate::error::BlinkError;
 use crate::instruction::accounts::EnterAccounts;
 use crate::instruction::EnterMeta;
 use crate::state::{self, Entry, Leaderboard};
@@ -28,22 +29,25 @@ pub fn enter<'a>(
         ctx.accounts.pda,
         program_id,
         vec![&accounts::PDA_SEED.to_le_bytes()],
-    )
-    .unwrap();
+    )?;
 
     let _data_bump_seed = accounts::check_program_data_account(
         ctx.accounts.data,
         program_id,
         vec![&accounts::DATA_SEED.to_le_bytes()],
-    )
-    .unwrap();
+    )?;
+
+    let _config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
 
     let _user_data_bump = accounts::check_program_data_account(
         ctx.accounts.user_data,
         program_id,
         vec![&ctx.accounts.user.key.to_bytes(), b"User"],
-    )
-    .unwrap();
+    )?;
 
     accounts::check_token_account(
         ctx.accounts.user,
@@ -53,7 +57,10 @@ pub fn enter<'a>(
     )?;
 
     let clock = Clock::get()?;
-    let current_date = (clock.unix_timestamp / (24 * 60 * 60)) as u32;
+    let game = utils::load_game(ctx.accounts.game, program_id, args.game)?;
+    utils::check_game_open(&game, clock.unix_timestamp)?;
+    let current_round = utils::get_round(&game, clock.unix_timestamp);
+    utils::check_submission_open(&game, current_round, clock.unix_timestamp)?;
 
     let entry_bump_seed = accounts::check_program_data_account(
         ctx.accounts.entry,
@@ -61,24 +68,29 @@ pub fn enter<'a>(
         vec![
             &ctx.accounts.user.key.to_bytes(),
             &args.game.to_le_bytes(),
-            &current_date.to_le_bytes(),
+            &current_round.to_le_bytes(),
         ],
-    )
-    .unwrap();
+    )?;
 
     let leaderboard_bump_seed = accounts::check_program_data_account(
         ctx.accounts.leaderboard,
         program_id,
         vec![
             &args.game.to_le_bytes(),
-            &current_date.to_le_bytes(),
+            &current_round.to_le_bytes(),
             b"Leaderboard",
         ],
-    )
-    .unwrap();
+    )?;
 
     accounts::check_system_program_key(ctx.accounts.system_program)?;
 
+    if args.uri.len() > state::MAX_URI_LENGTH {
+        msg!("uri is longer than {} bytes", state::MAX_URI_LENGTH);
+        return Err(BlinkError::UriTooLong.into());
+    }
+
+    let config = state::Config::try_from_slice(&ctx.accounts.config.data.borrow()[..])?;
+
     utils::create_user_data(
         ctx.accounts.user,
         ctx.accounts.user_data,
@@ -93,6 +105,8 @@ pub fn enter<'a>(
             positive_votes: 0,
             negative_votes: 0,
             reward_claimed: 0,
+            uri: args.uri,
+            content_hash: args.content_hash,
         };
 
         utils::create_program_account(
@@ -104,7 +118,7 @@ pub fn enter<'a>(
             vec![
                 &ctx.accounts.user.key.to_bytes(),
                 &args.game.to_le_bytes(),
-                &current_date.to_le_bytes(),
+                &current_round.to_le_bytes(),
             ],
         )?;
 
@@ -126,7 +140,7 @@ pub fn enter<'a>(
             ctx.accounts.user_token.key,
             ctx.accounts.pda.key,
             &[ctx.accounts.pda.key],
-            100,
+            game.entry_reward.unwrap_or(config.entry_reward),
             1,
         )
         .unwrap();
@@ -158,7 +172,7 @@ pub fn enter<'a>(
                 // only transfer if the ATA already exists
                 if **ref_bash.try_borrow_lamports()? > 0 {
                     utils::mint(
-                        50,
+                        config.entry_referral_reward,
                         ctx.accounts.token_2022,
                         ctx.accounts.bash_mint,
                         ref_bash,
@@ -173,9 +187,14 @@ pub fn enter<'a>(
             let temp: Leaderboard = Leaderboard {
                 account_type: state::AccountType::Leaderboard,
                 game: args.game,
-                date: current_date,
+                date: current_round,
                 entrants: Vec::new(),
                 scores: Vec::new(),
+                finalized: 0,
+                winners: Vec::new(),
+                prizes: Vec::new(),
+                payer: *ctx.accounts.user.key,
+                pool: 0,
             };
 
             utils::create_program_account(
@@ -186,7 +205,7 @@ pub fn enter<'a>(
                 to_vec(&temp).unwrap().len(),
                 vec![
                     &args.game.to_le_bytes(),
-                    &current_date.to_le_bytes(),
+                    &current_round.to_le_bytes(),
                     b"Leaderboard",
                 ],
             )?;
@@ -196,25 +215,70 @@ pub fn enter<'a>(
         }
 
         // check if we should add this entry to the leaderboard
-        let mut leaderboard =
-            Leaderboard::try_from_slice(&ctx.accounts.leaderboard.data.borrow()[..])?;
-        let user_data = state::User::try_from_slice(&ctx.accounts.user_data.data.borrow()[..])?;
+        let mut leaderboard = utils::read_leaderboard(ctx.accounts.leaderboard)?;
 
-        if leaderboard.scores.len() < 10 {
-            let old_size = ctx.accounts.leaderboard.data_len();
+        // the winners of a finalized round are fixed, so late entries can't join it
+        if leaderboard.finalized == 1 {
+            msg!("leaderboard has already been finalized");
+            return Err(BlinkError::LeaderboardFinalized.into());
+        }
 
-            leaderboard.entrants.push(user_data.user_id);
-            leaderboard.scores.push(0);
+        let user_data = utils::read_user_data(ctx.accounts.user_data)?;
 
-            utils::check_for_realloc(
-                ctx.accounts.leaderboard,
+        let old_size = ctx.accounts.leaderboard.data_len();
+
+        // games with an entry fee escrow it in the round's pool
+        if game.entry_fee > 0 {
+            let (pool, pool_token) =
+                utils::unwrap_pool_accounts(ctx.accounts.pool, ctx.accounts.pool_token)?;
+
+            accounts::check_bash_accounts(ctx.accounts.bash_mint, ctx.accounts.token_2022)?;
+
+            let _pool_bump_seed = utils::check_pool(
+                program_id,
+                args.game,
+                current_round,
+                pool,
+                pool_token,
+                ctx.accounts.bash_mint,
+                ctx.accounts.token_2022,
+            )?;
+
+            utils::create_ata(
                 ctx.accounts.user,
-                old_size,
-                to_vec(&leaderboard).unwrap().len(),
+                pool,
+                ctx.accounts.bash_mint,
+                pool_token,
+                ctx.accounts.token_2022,
             )?;
 
-            leaderboard.serialize(&mut &mut ctx.accounts.leaderboard.data.borrow_mut()[..])?;
+            utils::pay_into_pool(
+                game.entry_fee,
+                ctx.accounts.user,
+                ctx.accounts.user_token,
+                pool_token,
+                ctx.accounts.bash_mint,
+                ctx.accounts.token_2022,
+            )?;
+
+            leaderboard.pool += game.entry_fee;
         }
+
+        utils::update_leaderboard(
+            &mut leaderboard,
+            game.leaderboard_size as usize,
+            user_data.user_id,
+            0,
+        );
+
+        utils::check_for_realloc(
+            ctx.accounts.leaderboard,
+            ctx.accounts.user,
+            old_size,
+            to_vec(&leaderboard).unwrap().len(),
+        )?;
+
+        leaderboard.serialize(&mut &mut ctx.accounts.leaderboard.data.borrow_mut()[..])?;
     }
 
     Ok(())
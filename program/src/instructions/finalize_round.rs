@@ -0,0 +1,158 @@
+use crate::error::BlinkError;
+use crate::instruction::accounts::FinalizeRoundAccounts;
+use crate::instruction::RoundMeta;
+use crate::{accounts, utils};
+use borsh::{to_vec, BorshSerialize};
+use solana_program::clock::Clock;
+use solana_program::msg;
+use solana_program::sysvar::Sysvar;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+// anyone can finalize a round once voting has closed.  the user data accounts for each paid
+// rank are passed as remaining accounts in ranked order
+pub fn finalize_round<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: RoundMeta,
+) -> ProgramResult {
+    let ctx: crate::instruction::accounts::Context<FinalizeRoundAccounts> =
+        FinalizeRoundAccounts::context(accounts)?;
+
+    if !ctx.accounts.user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let _leaderboard_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.leaderboard,
+        program_id,
+        vec![
+            &args.game.to_le_bytes(),
+            &args.date.to_le_bytes(),
+            b"Leaderboard",
+        ],
+    )?;
+
+    accounts::check_system_program_key(ctx.accounts.system_program)?;
+
+    let game = utils::load_game(ctx.accounts.game, program_id, args.game)?;
+
+    let clock = Clock::get()?;
+    let voting_end = utils::get_voting_end(&game, args.date);
+
+    if clock.unix_timestamp < voting_end {
+        msg!("cannot finalize before voting closes at {}", voting_end);
+        return Err(BlinkError::RoundNotOver.into());
+    }
+
+    if **ctx.accounts.leaderboard.try_borrow_lamports()? == 0 {
+        msg!("no entrants in the leaderboard");
+        return Err(BlinkError::EmptyLeaderboard.into());
+    }
+
+    let mut leaderboard = utils::read_leaderboard(ctx.accounts.leaderboard)?;
+
+    if leaderboard.finalized == 1 {
+        msg!("leaderboard has already been finalized");
+        return Err(BlinkError::LeaderboardFinalized.into());
+    }
+
+    let ranked = utils::rank_entrants(&leaderboard);
+    msg!("have users sorted by scores: {:?}", ranked);
+
+    let (prizes, fees) = utils::get_round_payouts(
+        &game.prizes,
+        &ranked,
+        game.split_ties == 1,
+        leaderboard.pool,
+        game.rake_bps,
+    );
+
+    // only the ranks that win something are recorded, so large leaderboards still fit in one
+    // transaction.  first place is always needed so rounds without prizes still count a win
+    let paid = prizes
+        .iter()
+        .rposition(|&prize| prize > 0)
+        .map_or(1, |last| last + 1)
+        .min(ranked.len());
+
+    if ctx.remaining_accounts.len() != paid {
+        msg!(
+            "expected {} user accounts, got {}",
+            paid,
+            ctx.remaining_accounts.len()
+        );
+        return Err(BlinkError::WinnerMismatch.into());
+    }
+
+    for (rank, &(user_id, _)) in ranked.iter().take(paid).enumerate() {
+        let user_account = &ctx.remaining_accounts[rank];
+        let mut user_data = utils::read_user_data(user_account)?;
+
+        let _user_data_bump = accounts::check_program_data_account(
+            user_account,
+            program_id,
+            vec![&user_data.user_key.to_bytes(), b"User"],
+        )?;
+
+        if user_data.user_id != user_id {
+            msg!("user {} is not ranked {}", user_data.user_id, rank);
+            return Err(BlinkError::WinnerMismatch.into());
+        }
+
+        leaderboard.winners.push(user_data.user_key);
+        leaderboard.prizes.push(prizes[rank]);
+
+        // every paid rank counts as a win, and finalizing can only happen once
+        if prizes[rank] > 0 || rank == 0 {
+            user_data.total_wins += 1;
+            utils::write_user_data(user_account, ctx.accounts.user, &user_data)?;
+        }
+    }
+
+    // the rake and anything the prizes don't pay out of the pool goes to the fees account
+    if fees > 0 {
+        let (bash_mint, pool, pool_token, fees_token, token_2022) = utils::unwrap_fee_accounts(
+            ctx.accounts.bash_mint,
+            ctx.accounts.pool,
+            ctx.accounts.pool_token,
+            ctx.accounts.fees_token,
+            ctx.accounts.token_2022,
+        )?;
+
+        accounts::check_bash_accounts(bash_mint, token_2022)?;
+
+        let pool_bump_seed = utils::check_pool(
+            program_id, args.game, args.date, pool, pool_token, bash_mint, token_2022,
+        )?;
+
+        accounts::check_fees_token_account(bash_mint, fees_token, token_2022)?;
+
+        utils::pay_from_pool(
+            fees,
+            args.game,
+            args.date,
+            pool,
+            pool_token,
+            bash_mint,
+            fees_token,
+            token_2022,
+            pool_bump_seed,
+        )?;
+    }
+
+    leaderboard.finalized = 1;
+
+    utils::check_for_realloc(
+        ctx.accounts.leaderboard,
+        ctx.accounts.user,
+        ctx.accounts.leaderboard.data_len(),
+        to_vec(&leaderboard).unwrap().len(),
+    )?;
+
+    leaderboard.serialize(&mut &mut ctx.accounts.leaderboard.data.borrow_mut()[..])?;
+
+    Ok(())
+}
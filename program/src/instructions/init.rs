@@ -1,7 +1,8 @@
+use crate::error::BlinkError;
 use crate::instruction::accounts::InitAccounts;
-use crate::state::{self, ProgramStats};
+use crate::state::{self, Config, ProgramStats};
 use crate::{accounts, utils};
-use borsh::to_vec;
+use borsh::{to_vec, BorshSerialize};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
     pubkey::Pubkey,
@@ -14,22 +15,30 @@ pub fn init<'a>(program_id: &Pubkey, accounts: &'a [AccountInfo<'a>]) -> Program
         return Err(ProgramError::MissingRequiredSignature);
     }
 
+    if ctx.accounts.user.key != &accounts::daoplays_account::ID {
+        return Err(BlinkError::NotAdmin.into());
+    }
+
     let data_bump_seed = accounts::check_program_data_account(
         ctx.accounts.data,
         program_id,
         vec![&accounts::DATA_SEED.to_le_bytes()],
-    )
-    .unwrap();
+    )?;
 
     let pda_bump_seed = accounts::check_program_data_account(
         ctx.accounts.pda,
         program_id,
         vec![&accounts::PDA_SEED.to_le_bytes()],
-    )
-    .unwrap();
+    )?;
+
+    let config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
 
     if ctx.accounts.token_mint.key != &accounts::bash_mint::ID {
-        return Err(ProgramError::InvalidAccountData);
+        return Err(BlinkError::InvalidMint.into());
     }
 
     accounts::check_system_program_key(ctx.accounts.system_program)?;
@@ -58,6 +67,31 @@ pub fn init<'a>(program_id: &Pubkey, accounts: &'a [AccountInfo<'a>]) -> Program
         vec![&accounts::DATA_SEED.to_le_bytes()],
     )?;
 
+    let config: Config = Config {
+        account_type: state::AccountType::Config,
+        admin: *ctx.accounts.user.key,
+        pending_admin: Pubkey::default(),
+        paused: 0,
+        entry_reward: 100,
+        entry_referral_reward: 50,
+        vote_reward: 10,
+        vote_referral_reward: 10,
+        max_rewarded_votes_per_day: 50,
+        retention_period: 30 * 24 * 60 * 60,
+        market_fee_bps: 250,
+    };
+
+    utils::create_program_account(
+        ctx.accounts.user,
+        ctx.accounts.config,
+        program_id,
+        config_bump_seed,
+        to_vec(&config).unwrap().len(),
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
+
+    config.serialize(&mut &mut ctx.accounts.config.data.borrow_mut()[..])?;
+
     //create $BASH mint
     utils::create_2022_token(
         ctx.accounts.user,
@@ -1,8 +1,10 @@
+use crate::error::BlinkError;
 use crate::instruction::accounts::ListItemAccounts;
 use crate::instruction::ListMeta;
 use crate::{accounts, state, utils};
 use borsh::{to_vec, BorshDeserialize, BorshSerialize};
 use mpl_core::instructions::TransferV1CpiBuilder;
+use solana_program::msg;
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
     pubkey::Pubkey,
@@ -25,15 +27,19 @@ pub fn list_item<'a>(
         ctx.accounts.pda,
         program_id,
         vec![&accounts::PDA_SEED.to_le_bytes()],
-    )
-    .unwrap();
+    )?;
 
     let listing_bump_seed = accounts::check_program_data_account(
         ctx.accounts.listing,
         program_id,
         vec![&ctx.accounts.item.key.to_bytes(), b"Listing"],
-    )
-    .unwrap();
+    )?;
+
+    let _config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
 
     accounts::check_token_account(
         ctx.accounts.user,
@@ -63,6 +69,8 @@ pub fn list_item<'a>(
             price: args.price,
             quantity: 0,
             bundle_size: 1,
+            seller: *ctx.accounts.user.key,
+            payment: args.payment,
         };
 
         utils::create_program_account(
@@ -77,16 +85,41 @@ pub fn list_item<'a>(
         listing.serialize(&mut &mut ctx.accounts.listing.data.borrow_mut()[..])?;
     }
 
-    let mut listing = state::Listing::try_from_slice(&ctx.accounts.listing.data.borrow()[..])?;
+    let config = state::Config::try_from_slice(&ctx.accounts.config.data.borrow()[..])?;
+    let mut listing = utils::read_listing(ctx.accounts.listing, &config)?;
+    accounts::check_listing_authority(ctx.accounts.user, &listing, &config)?;
+
+    if args.payment > 1 {
+        return Err(BlinkError::InvalidPayment.into());
+    }
+
+    listing.payment = args.payment;
 
     //token
     if args.item_type == 1 {
-        listing.quantity += args.quantity;
-        listing.price = args.price;
-
         let mint_data = ctx.accounts.item.data.borrow();
         let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
 
+        let bundle_size = if args.bundle_size == 0 {
+            utils::get_default_bundle_size(mint.base.decimals)?
+        } else {
+            args.bundle_size
+        };
+
+        listing.quantity += args.quantity;
+        listing.price = args.price;
+        listing.bundle_size = bundle_size;
+
+        // the whole listing has to be sold as complete bundles
+        if listing.quantity.checked_rem(bundle_size) != Some(0) {
+            msg!(
+                "{} is not a multiple of the bundle size {}",
+                listing.quantity,
+                bundle_size
+            );
+            return Err(BlinkError::PartialBundle.into());
+        }
+
         accounts::check_token_account(
             ctx.accounts.pda,
             ctx.accounts.item,
@@ -130,7 +163,7 @@ pub fn list_item<'a>(
             .invoke_signed(&[&[&accounts::PDA_SEED.to_le_bytes(), &[pda_bump_seed]]])?;
     }
 
-    listing.serialize(&mut &mut ctx.accounts.listing.data.borrow_mut()[..])?;
+    utils::write_listing(ctx.accounts.listing, ctx.accounts.user, &listing)?;
 
     Ok(())
 }
@@ -1,13 +1,43 @@
+pub mod accept_admin;
+pub mod change_vote;
 pub mod claim_prize;
+pub mod claim_prizes;
+pub mod close_entry;
+pub mod close_leaderboard;
+pub mod delist_item;
+pub mod distribute_prizes;
 pub mod enter;
+pub mod finalize_round;
 pub mod init;
 pub mod list_item;
 pub mod purchase_item;
+pub mod register_game;
+pub mod retract_vote;
+pub mod set_paused;
+pub mod set_profile;
+pub mod transfer_admin;
+pub mod update_config;
+pub mod update_game;
 pub mod vote;
 
+pub use accept_admin::*;
+pub use change_vote::*;
 pub use claim_prize::*;
+pub use claim_prizes::*;
+pub use close_entry::*;
+pub use close_leaderboard::*;
+pub use delist_item::*;
+pub use distribute_prizes::*;
 pub use enter::*;
+pub use finalize_round::*;
 pub use init::*;
 pub use list_item::*;
 pub use purchase_item::*;
+pub use register_game::*;
+pub use retract_vote::*;
+pub use set_paused::*;
+pub use set_profile::*;
+pub use transfer_admin::*;
+pub use update_config::*;
+pub use update_game::*;
 pub use vote::*;
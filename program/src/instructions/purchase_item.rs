@@ -1,21 +1,130 @@
-use crate::instruction::accounts::PurchaseItemAccounts;
+use crate::error::BlinkError;
+use crate::instruction::accounts::{Context, PurchaseItemAccounts};
 use crate::instruction::PurchaseMeta;
 use crate::{accounts, state, utils};
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::BorshDeserialize;
 use mpl_core::instructions::TransferV1CpiBuilder;
+use solana_program::msg;
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
     pubkey::Pubkey,
 };
 use spl_token_2022::extension::StateWithExtensions;
 
+// the buyer's $BASH is either burned or paid to the seller, less the market fee
+fn pay_for_listing<'a>(
+    ctx: &Context<'a, PurchaseItemAccounts<'a>>,
+    listing: &state::Listing,
+    config: &state::Config,
+    price: u64,
+    pda_bump_seed: u8,
+) -> ProgramResult {
+    if listing.payment == 0 {
+        return utils::burn(
+            price,
+            ctx.accounts.token_2022,
+            ctx.accounts.bash_mint,
+            ctx.accounts.user_bash,
+            ctx.accounts.user,
+        );
+    }
+
+    let (seller, seller_bash) = match (ctx.accounts.seller, ctx.accounts.seller_bash) {
+        (Some(seller), Some(seller_bash)) => (seller, seller_bash),
+        _ => return Err(BlinkError::MissingAccount.into()),
+    };
+
+    if seller.key != &listing.seller {
+        msg!("expected seller {}", listing.seller);
+        return Err(BlinkError::SellerMismatch.into());
+    }
+
+    accounts::check_token_account(
+        seller,
+        ctx.accounts.bash_mint,
+        seller_bash,
+        ctx.accounts.token_2022,
+    )?;
+
+    utils::create_ata(
+        ctx.accounts.user,
+        seller,
+        ctx.accounts.bash_mint,
+        seller_bash,
+        ctx.accounts.token_2022,
+    )?;
+
+    let decimals = {
+        let mint_data = ctx.accounts.bash_mint.data.borrow();
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?
+            .base
+            .decimals
+    };
+
+    let fee = ((price as u128) * (config.market_fee_bps as u128) / 10000) as u64;
+
+    if fee > 0 {
+        let fees_bash = match ctx.accounts.fees_bash {
+            Some(fees_bash) => fees_bash,
+            None => return Err(BlinkError::MissingAccount.into()),
+        };
+
+        accounts::check_fees_token_account(
+            ctx.accounts.bash_mint,
+            fees_bash,
+            ctx.accounts.token_2022,
+        )?;
+
+        utils::transfer_tokens(
+            true,
+            fee,
+            ctx.accounts.user_bash,
+            ctx.accounts.bash_mint,
+            fees_bash,
+            ctx.accounts.user,
+            ctx.accounts.token_2022,
+            pda_bump_seed,
+            &vec![&accounts::PDA_SEED.to_le_bytes()],
+            decimals,
+            &Vec::new(),
+        )?;
+    }
+
+    utils::transfer_tokens(
+        true,
+        price - fee,
+        ctx.accounts.user_bash,
+        ctx.accounts.bash_mint,
+        seller_bash,
+        ctx.accounts.user,
+        ctx.accounts.token_2022,
+        pda_bump_seed,
+        &vec![&accounts::PDA_SEED.to_le_bytes()],
+        decimals,
+        &Vec::new(),
+    )
+}
+
+// protects the buyer from the price being raised before their purchase lands
+fn check_max_price(price: u64, max_total_price: u64) -> ProgramResult {
+    if price > max_total_price {
+        msg!(
+            "price {} is above the maximum of {}",
+            price,
+            max_total_price
+        );
+        return Err(BlinkError::SlippageExceeded.into());
+    }
+
+    Ok(())
+}
+
 pub fn purchase_item<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
     args: PurchaseMeta,
 ) -> ProgramResult {
-    let ctx: crate::instruction::accounts::Context<PurchaseItemAccounts> =
-        PurchaseItemAccounts::context(accounts)?;
+    let ctx: Context<PurchaseItemAccounts> = PurchaseItemAccounts::context(accounts)?;
 
     if !ctx.accounts.user.is_signer {
         return Err(ProgramError::MissingRequiredSignature);
@@ -25,15 +134,19 @@ pub fn purchase_item<'a>(
         ctx.accounts.pda,
         program_id,
         vec![&accounts::PDA_SEED.to_le_bytes()],
-    )
-    .unwrap();
+    )?;
 
     let _listing_bump_seed = accounts::check_program_data_account(
         ctx.accounts.listing,
         program_id,
         vec![&ctx.accounts.item.key.to_bytes(), b"Listing"],
-    )
-    .unwrap();
+    )?;
+
+    let _config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
 
     accounts::check_token_account(
         ctx.accounts.user,
@@ -45,21 +158,18 @@ pub fn purchase_item<'a>(
     let listing_2022 = accounts::check_token_program_key(ctx.accounts.listing_tp)?;
 
     if ctx.accounts.bash_mint.key != &accounts::bash_mint::ID {
-        return Err(ProgramError::InvalidAccountData);
+        return Err(BlinkError::InvalidMint.into());
     }
 
-    let mut listing = state::Listing::try_from_slice(&ctx.accounts.listing.data.borrow()[..])?;
+    let config = state::Config::try_from_slice(&ctx.accounts.config.data.borrow()[..])?;
+    let mut listing = utils::read_listing(ctx.accounts.listing, &config)?;
 
     if listing.item_address != *ctx.accounts.item.key {
-        return Err(ProgramError::InvalidAccountData);
+        return Err(BlinkError::ListingMismatch.into());
     }
 
     accounts::check_system_program_key(ctx.accounts.system_program)?;
 
-    if listing.item_address != *ctx.accounts.item.key {
-        return Err(ProgramError::InvalidAccountData);
-    }
-
     let quantity = args.quantity.min(listing.quantity);
 
     //token
@@ -67,16 +177,16 @@ pub fn purchase_item<'a>(
         let mint_data = ctx.accounts.item.data.borrow();
         let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
 
-        let float_q = quantity as f64 / 10_f64.powi(mint.base.decimals as i32);
-        let price = (float_q * listing.price as f64) as u64;
+        let bundle_size = if listing.bundle_size == 0 {
+            utils::get_default_bundle_size(mint.base.decimals)?
+        } else {
+            listing.bundle_size
+        };
 
-        utils::burn(
-            price,
-            ctx.accounts.token_2022,
-            ctx.accounts.bash_mint,
-            ctx.accounts.user_bash,
-            ctx.accounts.user,
-        )?;
+        let price = utils::get_bundle_price(listing.price, quantity, bundle_size)?;
+        check_max_price(price, args.max_total_price)?;
+
+        pay_for_listing(&ctx, &listing, &config, price, pda_bump_seed)?;
 
         accounts::check_token_account(
             ctx.accounts.user,
@@ -114,12 +224,14 @@ pub fn purchase_item<'a>(
             &Vec::new(),
         )?;
 
-        listing.quantity -= args.quantity;
-        listing.serialize(&mut &mut ctx.accounts.listing.data.borrow_mut()[..])?;
+        listing.quantity -= quantity;
+        utils::write_listing(ctx.accounts.listing, ctx.accounts.user, &listing)?;
     }
 
     //core asset
     if listing.item_type == 2 {
+        check_max_price(listing.price, args.max_total_price)?;
+
         let _transfer = TransferV1CpiBuilder::new(ctx.accounts.core)
             .asset(ctx.accounts.item)
             .authority(Some(ctx.accounts.pda))
@@ -128,16 +240,10 @@ pub fn purchase_item<'a>(
             .collection(Some(ctx.accounts.collection))
             .invoke_signed(&[&[&accounts::PDA_SEED.to_le_bytes(), &[pda_bump_seed]]])?;
 
-        utils::burn(
-            listing.price,
-            ctx.accounts.token_2022,
-            ctx.accounts.bash_mint,
-            ctx.accounts.user_bash,
-            ctx.accounts.user,
-        )?;
+        pay_for_listing(&ctx, &listing, &config, listing.price, pda_bump_seed)?;
 
         listing.quantity = 0;
-        listing.serialize(&mut &mut ctx.accounts.listing.data.borrow_mut()[..])?;
+        utils::write_listing(ctx.accounts.listing, ctx.accounts.user, &listing)?;
 
         let account_lamports = **ctx.accounts.listing.try_borrow_lamports()?;
 
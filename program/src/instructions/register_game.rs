@@ -0,0 +1,167 @@
+use crate::error::BlinkError;
+use crate::instruction::accounts::RegisterGameAccounts;
+use crate::instruction::GameMeta;
+use crate::state::{self, Game, PrizeTable};
+use crate::{accounts, utils};
+use borsh::{to_vec, BorshDeserialize, BorshSerialize};
+use solana_program::msg;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+// validates the game settings shared by RegisterGame and UpdateGame
+pub fn build_game(args: GameMeta) -> Result<Game, ProgramError> {
+    if args.name.len() > state::MAX_GAME_NAME_LENGTH {
+        msg!("name is longer than {} bytes", state::MAX_GAME_NAME_LENGTH);
+        return Err(BlinkError::GameNameTooLong.into());
+    }
+
+    if args.description_uri.len() > state::MAX_URI_LENGTH {
+        msg!("uri is longer than {} bytes", state::MAX_URI_LENGTH);
+        return Err(BlinkError::UriTooLong.into());
+    }
+
+    if args.end_time != 0 && args.end_time <= args.start_time {
+        msg!(
+            "game ends at {} before it starts at {}",
+            args.end_time,
+            args.start_time
+        );
+        return Err(BlinkError::InvalidGameWindow.into());
+    }
+
+    if args.round_length == 0 || args.round_offset >= args.round_length {
+        msg!(
+            "invalid round length {} with offset {}",
+            args.round_length,
+            args.round_offset
+        );
+        return Err(BlinkError::InvalidRoundLength.into());
+    }
+
+    // voting can't close while entries are still being accepted
+    if args.submission_length == 0
+        || args.submission_length > args.round_length
+        || args.voting_length == 0
+        || (args.voting_delay as u64 + args.voting_length as u64) < args.submission_length as u64
+    {
+        msg!(
+            "invalid phases, submission {} voting {} after {}",
+            args.submission_length,
+            args.voting_length,
+            args.voting_delay
+        );
+        return Err(BlinkError::InvalidPhases.into());
+    }
+
+    if args.leaderboard_size == 0 || args.leaderboard_size > state::MAX_LEADERBOARD_SIZE {
+        msg!(
+            "leaderboard size must be between 1 and {}",
+            state::MAX_LEADERBOARD_SIZE
+        );
+        return Err(BlinkError::InvalidLeaderboardSize.into());
+    }
+
+    // fee games pay out shares of the pool rather than fixed amounts
+    let pool_prizes = matches!(args.prizes, PrizeTable::Pool { .. });
+    if args.rake_bps > 10000 || (args.entry_fee > 0 && !pool_prizes) {
+        msg!(
+            "invalid entry fee {} with rake {}",
+            args.entry_fee,
+            args.rake_bps
+        );
+        return Err(BlinkError::InvalidEntryFee.into());
+    }
+
+    let valid_prizes = match &args.prizes {
+        PrizeTable::Fixed(amounts) => {
+            amounts.len() <= args.leaderboard_size as usize
+                && amounts.len() <= state::MAX_PRIZE_RANKS
+        }
+        PrizeTable::Pool { amount: _, shares } => {
+            shares.len() <= args.leaderboard_size as usize
+                && shares.len() <= state::MAX_PRIZE_RANKS
+                && shares.iter().map(|&share| share as u32).sum::<u32>() <= 10000
+        }
+    };
+
+    if !valid_prizes {
+        msg!("invalid prize table {:?}", args.prizes);
+        return Err(BlinkError::InvalidPrizeTable.into());
+    }
+
+    Ok(Game {
+        account_type: state::AccountType::Game,
+        game: args.game,
+        name: args.name,
+        description_uri: args.description_uri,
+        active: args.active,
+        entry_reward: args.entry_reward,
+        vote_reward: args.vote_reward,
+        entry_fee: args.entry_fee,
+        rake_bps: args.rake_bps,
+        start_time: args.start_time,
+        end_time: args.end_time,
+        round_length: args.round_length,
+        round_offset: args.round_offset,
+        submission_length: args.submission_length,
+        voting_delay: args.voting_delay,
+        voting_length: args.voting_length,
+        leaderboard_size: args.leaderboard_size,
+        prizes: args.prizes,
+        split_ties: args.split_ties,
+    })
+}
+
+pub fn register_game<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: GameMeta,
+) -> ProgramResult {
+    let ctx: crate::instruction::accounts::Context<RegisterGameAccounts> =
+        RegisterGameAccounts::context(accounts)?;
+
+    if !ctx.accounts.user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let _config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
+
+    let game_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.game,
+        program_id,
+        vec![&args.game.to_le_bytes(), b"Game"],
+    )?;
+
+    accounts::check_system_program_key(ctx.accounts.system_program)?;
+
+    let config = state::Config::try_from_slice(&ctx.accounts.config.data.borrow()[..])?;
+
+    accounts::check_admin(ctx.accounts.user, &config)?;
+
+    if **ctx.accounts.game.try_borrow_lamports()? > 0 {
+        msg!("game {} is already registered", args.game);
+        return Err(BlinkError::GameAlreadyRegistered.into());
+    }
+
+    let game_id = args.game;
+    let game = build_game(args)?;
+
+    utils::create_program_account(
+        ctx.accounts.user,
+        ctx.accounts.game,
+        program_id,
+        game_bump_seed,
+        to_vec(&game).unwrap().len(),
+        vec![&game_id.to_le_bytes(), b"Game"],
+    )?;
+
+    game.serialize(&mut &mut ctx.accounts.game.data.borrow_mut()[..])?;
+
+    Ok(())
+}
@@ -0,0 +1,11 @@
+use crate::instruction::RetractVoteMeta;
+use crate::instructions;
+use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, pubkey::Pubkey};
+
+pub fn retract_vote<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: RetractVoteMeta,
+) -> ProgramResult {
+    instructions::update_vote(program_id, accounts, args.game, args.date, 0)
+}
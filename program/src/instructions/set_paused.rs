@@ -0,0 +1,45 @@
+use crate::error::BlinkError;
+use crate::instruction::accounts::SetPausedAccounts;
+use crate::instruction::SetPausedMeta;
+use crate::{accounts, state};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::msg;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+pub fn set_paused<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetPausedMeta,
+) -> ProgramResult {
+    let ctx: crate::instruction::accounts::Context<SetPausedAccounts> =
+        SetPausedAccounts::context(accounts)?;
+
+    if !ctx.accounts.user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let _config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
+
+    let mut config = state::Config::try_from_slice(&ctx.accounts.config.data.borrow()[..])?;
+
+    accounts::check_admin(ctx.accounts.user, &config)?;
+
+    if args.paused > 1 {
+        msg!("invalid paused value {}", args.paused);
+        return Err(BlinkError::InvalidPaused.into());
+    }
+
+    msg!("set paused {}", args.paused);
+    config.paused = args.paused;
+
+    config.serialize(&mut &mut ctx.accounts.config.data.borrow_mut()[..])?;
+
+    Ok(())
+}
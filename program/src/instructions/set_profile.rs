@@ -0,0 +1,165 @@
+use crate::error::BlinkError;
+use crate::instruction::accounts::SetProfileAccounts;
+use crate::instruction::SetProfileMeta;
+use crate::state::Handle;
+use crate::{accounts, state, utils};
+use borsh::{to_vec, BorshDeserialize, BorshSerialize};
+use solana_program::msg;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+pub fn set_profile<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: SetProfileMeta,
+) -> ProgramResult {
+    let ctx: crate::instruction::accounts::Context<SetProfileAccounts> =
+        SetProfileAccounts::context(accounts)?;
+
+    if !ctx.accounts.user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let _data_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.data,
+        program_id,
+        vec![&accounts::DATA_SEED.to_le_bytes()],
+    )?;
+
+    let _user_data_bump = accounts::check_program_data_account(
+        ctx.accounts.user_data,
+        program_id,
+        vec![&ctx.accounts.user.key.to_bytes(), b"User"],
+    )?;
+
+    accounts::check_system_program_key(ctx.accounts.system_program)?;
+
+    let twitter = utils::normalize_handle(&args.twitter)?;
+
+    if twitter.len() > state::MAX_TWITTER_LENGTH {
+        msg!(
+            "twitter handle is longer than {} bytes",
+            state::MAX_TWITTER_LENGTH
+        );
+        return Err(BlinkError::TwitterTooLong.into());
+    }
+
+    if args.display_name.len() > state::MAX_DISPLAY_NAME_LENGTH {
+        msg!(
+            "display name is longer than {} bytes",
+            state::MAX_DISPLAY_NAME_LENGTH
+        );
+        return Err(BlinkError::DisplayNameTooLong.into());
+    }
+
+    if args.avatar_uri.len() > state::MAX_URI_LENGTH {
+        msg!("avatar uri is longer than {} bytes", state::MAX_URI_LENGTH);
+        return Err(BlinkError::UriTooLong.into());
+    }
+
+    utils::create_user_data(
+        ctx.accounts.user,
+        ctx.accounts.user_data,
+        ctx.accounts.data,
+        program_id,
+    )?;
+
+    let mut user_data = utils::read_user_data(ctx.accounts.user_data)?;
+    let old_size = ctx.accounts.user_data.data_len();
+
+    // profiles saved before handles were validated never had a handle account
+    let old_twitter = utils::normalize_handle(&user_data.twitter).unwrap_or_default();
+
+    if old_twitter != twitter {
+        // release the current handle so someone else can claim it
+        if !old_twitter.is_empty() {
+            let old_handle = match ctx.accounts.old_handle {
+                Some(old_handle) => old_handle,
+                None => {
+                    msg!("old handle account required to release {}", old_twitter);
+                    return Err(BlinkError::MissingAccount.into());
+                }
+            };
+
+            let _old_handle_bump = accounts::check_program_data_account(
+                old_handle,
+                program_id,
+                vec![old_twitter.as_bytes(), b"Handle"],
+            )?;
+
+            // handles set before the registry existed have no account to close
+            if old_handle.owner == program_id {
+                utils::close_program_account(old_handle, ctx.accounts.user)?;
+            }
+        }
+
+        if !twitter.is_empty() {
+            let handle = match ctx.accounts.handle {
+                Some(handle) => handle,
+                None => {
+                    msg!("handle account required to claim {}", twitter);
+                    return Err(BlinkError::MissingAccount.into());
+                }
+            };
+
+            let handle_bump = accounts::check_program_data_account(
+                handle,
+                program_id,
+                vec![twitter.as_bytes(), b"Handle"],
+            )?;
+
+            // anyone can send lamports to the handle's address, so only a registered handle
+            // counts as claimed
+            if handle.owner == program_id
+                && Handle::try_from_slice(&handle.data.borrow()[..]).is_ok()
+            {
+                msg!("handle {} is already claimed", twitter);
+                return Err(BlinkError::HandleTaken.into());
+            }
+
+            let handle_data: Handle = Handle {
+                account_type: state::AccountType::Handle,
+                user_key: *ctx.accounts.user.key,
+            };
+
+            if **handle.try_borrow_lamports()? > 0 {
+                utils::create_funded_program_account(
+                    ctx.accounts.user,
+                    handle,
+                    program_id,
+                    handle_bump,
+                    to_vec(&handle_data).unwrap().len(),
+                    vec![twitter.as_bytes(), b"Handle"],
+                )?;
+            } else {
+                utils::create_program_account(
+                    ctx.accounts.user,
+                    handle,
+                    program_id,
+                    handle_bump,
+                    to_vec(&handle_data).unwrap().len(),
+                    vec![twitter.as_bytes(), b"Handle"],
+                )?;
+            }
+
+            handle_data.serialize(&mut &mut handle.data.borrow_mut()[..])?;
+        }
+    }
+
+    user_data.twitter = twitter;
+    user_data.display_name = args.display_name;
+    user_data.avatar_uri = args.avatar_uri;
+
+    utils::check_for_realloc(
+        ctx.accounts.user_data,
+        ctx.accounts.user,
+        old_size,
+        to_vec(&user_data).unwrap().len(),
+    )?;
+
+    user_data.serialize(&mut &mut ctx.accounts.user_data.data.borrow_mut()[..])?;
+
+    Ok(())
+}
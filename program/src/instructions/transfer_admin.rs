@@ -0,0 +1,41 @@
+use crate::instruction::accounts::TransferAdminAccounts;
+use crate::instruction::TransferAdminMeta;
+use crate::{accounts, state};
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::msg;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+// the new admin only takes over once they have signed AcceptAdmin, so a mistyped key
+// can't lock everyone out of the program
+pub fn transfer_admin<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: TransferAdminMeta,
+) -> ProgramResult {
+    let ctx: crate::instruction::accounts::Context<TransferAdminAccounts> =
+        TransferAdminAccounts::context(accounts)?;
+
+    if !ctx.accounts.user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let _config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
+
+    let mut config = state::Config::try_from_slice(&ctx.accounts.config.data.borrow()[..])?;
+
+    accounts::check_admin(ctx.accounts.user, &config)?;
+
+    msg!("pending admin {}", args.new_admin);
+    config.pending_admin = args.new_admin;
+
+    config.serialize(&mut &mut ctx.accounts.config.data.borrow_mut()[..])?;
+
+    Ok(())
+}
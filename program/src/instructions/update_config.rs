@@ -0,0 +1,85 @@
+use crate::error::BlinkError;
+use crate::instruction::accounts::UpdateConfigAccounts;
+use crate::instruction::UpdateConfigMeta;
+use crate::state::{self, Config};
+use crate::{accounts, utils};
+use borsh::{to_vec, BorshDeserialize, BorshSerialize};
+use solana_program::msg;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+pub fn update_config<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: UpdateConfigMeta,
+) -> ProgramResult {
+    let ctx: crate::instruction::accounts::Context<UpdateConfigAccounts> =
+        UpdateConfigAccounts::context(accounts)?;
+
+    if !ctx.accounts.user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
+
+    accounts::check_system_program_key(ctx.accounts.system_program)?;
+
+    let mut config: Config = Config {
+        account_type: state::AccountType::Config,
+        admin: accounts::daoplays_account::ID,
+        pending_admin: Pubkey::default(),
+        paused: 0,
+        entry_reward: args.entry_reward,
+        entry_referral_reward: args.entry_referral_reward,
+        vote_reward: args.vote_reward,
+        vote_referral_reward: args.vote_referral_reward,
+        max_rewarded_votes_per_day: args.max_rewarded_votes_per_day,
+        retention_period: args.retention_period,
+        market_fee_bps: args.market_fee_bps,
+    };
+
+    // keep the current admin and pause state if the config already exists
+    if **ctx.accounts.config.try_borrow_lamports()? > 0 {
+        let current = Config::try_from_slice(&ctx.accounts.config.data.borrow()[..])?;
+        config.admin = current.admin;
+        config.pending_admin = current.pending_admin;
+        config.paused = current.paused;
+    }
+
+    if config.market_fee_bps > 10000 {
+        msg!("market fee {} is more than 100%", config.market_fee_bps);
+        return Err(BlinkError::InvalidMarketFee.into());
+    }
+
+    if ctx.accounts.user.key != &config.admin {
+        msg!("only the admin can update the config");
+        return Err(BlinkError::NotAdmin.into());
+    }
+
+    // programs that were initialised before the config existed create it here
+    utils::create_program_account(
+        ctx.accounts.user,
+        ctx.accounts.config,
+        program_id,
+        config_bump_seed,
+        to_vec(&config).unwrap().len(),
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
+
+    utils::check_for_realloc(
+        ctx.accounts.config,
+        ctx.accounts.user,
+        ctx.accounts.config.data_len(),
+        to_vec(&config).unwrap().len(),
+    )?;
+
+    config.serialize(&mut &mut ctx.accounts.config.data.borrow_mut()[..])?;
+
+    Ok(())
+}
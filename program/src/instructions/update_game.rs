@@ -0,0 +1,71 @@
+use crate::error::BlinkError;
+use crate::instruction::accounts::UpdateGameAccounts;
+use crate::instruction::GameMeta;
+use crate::{accounts, instructions, state, utils};
+use borsh::{to_vec, BorshDeserialize, BorshSerialize};
+use solana_program::clock::Clock;
+use solana_program::msg;
+use solana_program::sysvar::Sysvar;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+pub fn update_game<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    args: GameMeta,
+) -> ProgramResult {
+    let ctx: crate::instruction::accounts::Context<UpdateGameAccounts> =
+        UpdateGameAccounts::context(accounts)?;
+
+    if !ctx.accounts.user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let _config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
+
+    let _game_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.game,
+        program_id,
+        vec![&args.game.to_le_bytes(), b"Game"],
+    )?;
+
+    accounts::check_system_program_key(ctx.accounts.system_program)?;
+
+    let config = state::Config::try_from_slice(&ctx.accounts.config.data.borrow()[..])?;
+
+    accounts::check_admin(ctx.accounts.user, &config)?;
+
+    let current = utils::read_game(ctx.accounts.game, program_id, args.game)?;
+    let game = instructions::build_game(args)?;
+
+    // entries and leaderboards are keyed by round, so existing rounds would be reinterpreted
+    let clock = Clock::get()?;
+    let retimed = game.start_time != current.start_time
+        || game.round_length != current.round_length
+        || game.round_offset != current.round_offset
+        || game.submission_length != current.submission_length
+        || game.voting_delay != current.voting_delay
+        || game.voting_length != current.voting_length;
+
+    if retimed && clock.unix_timestamp >= current.start_time {
+        msg!("game {} started at {}", current.game, current.start_time);
+        return Err(BlinkError::GameStarted.into());
+    }
+
+    utils::check_for_realloc(
+        ctx.accounts.game,
+        ctx.accounts.user,
+        ctx.accounts.game.data_len(),
+        to_vec(&game).unwrap().len(),
+    )?;
+
+    game.serialize(&mut &mut ctx.accounts.game.data.borrow_mut()[..])?;
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
-use crate::instruction::accounts::VoteAccounts;
+use crate::error::BlinkError;
+use crate::instruction::accounts::{ChangeVoteAccounts, VoteAccounts};
 use crate::instruction::VoteMeta;
-use crate::state::Leaderboard;
+use crate::state::{Activity, Entry, Leaderboard, User, VoteRecord};
 use crate::{accounts, state, utils};
 use borsh::{to_vec, BorshDeserialize, BorshSerialize};
 use solana_program::clock::Clock;
@@ -12,6 +13,178 @@ use solana_program::{
     pubkey::Pubkey,
 };
 
+pub fn add_vote(
+    vote: u8,
+    entry: &mut Entry,
+    creator_data: &mut User,
+    voter_data: &mut User,
+) -> ProgramResult {
+    if vote == 1 {
+        entry.positive_votes += 1;
+        creator_data.total_positive_votes += 1;
+        voter_data.total_positive_voted += 1;
+    } else if vote == 2 {
+        entry.negative_votes += 1;
+        creator_data.total_negative_votes += 1;
+        voter_data.total_negative_voted += 1;
+    } else {
+        return Err(BlinkError::InvalidVote.into());
+    }
+
+    Ok(())
+}
+
+pub fn remove_vote(
+    vote: u8,
+    entry: &mut Entry,
+    creator_data: &mut User,
+    voter_data: &mut User,
+) -> ProgramResult {
+    if vote == 1 {
+        entry.positive_votes -= 1;
+        creator_data.total_positive_votes -= 1;
+        voter_data.total_positive_voted -= 1;
+    } else if vote == 2 {
+        entry.negative_votes -= 1;
+        creator_data.total_negative_votes -= 1;
+        voter_data.total_negative_voted -= 1;
+    } else {
+        return Err(BlinkError::InvalidVote.into());
+    }
+
+    Ok(())
+}
+
+// ChangeVote and RetractVote take the same accounts and both rewrite an existing vote record,
+// where a vote of 0 retracts it
+pub fn update_vote<'a>(
+    program_id: &Pubkey,
+    accounts: &'a [AccountInfo<'a>],
+    game_id: u8,
+    date: u32,
+    vote: u8,
+) -> ProgramResult {
+    let ctx: crate::instruction::accounts::Context<ChangeVoteAccounts> =
+        ChangeVoteAccounts::context(accounts)?;
+
+    if !ctx.accounts.user.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let clock = Clock::get()?;
+    let game = utils::load_game(ctx.accounts.game, program_id, game_id)?;
+    // votes can only be changed while the voting for the entry's round is open
+    utils::check_voting_open(&game, date, clock.unix_timestamp)?;
+
+    let _entry_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.entry,
+        program_id,
+        vec![
+            &ctx.accounts.creator.key.to_bytes(),
+            &game_id.to_le_bytes(),
+            &date.to_le_bytes(),
+        ],
+    )?;
+
+    let _vote_record_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.vote_record,
+        program_id,
+        vec![
+            &ctx.accounts.user.key.to_bytes(),
+            &ctx.accounts.entry.key.to_bytes(),
+            b"Vote",
+        ],
+    )?;
+
+    let _leaderboard_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.leaderboard,
+        program_id,
+        vec![&game_id.to_le_bytes(), &date.to_le_bytes(), b"Leaderboard"],
+    )?;
+
+    let _user_data_bump = accounts::check_program_data_account(
+        ctx.accounts.user_data,
+        program_id,
+        vec![&ctx.accounts.user.key.to_bytes(), b"User"],
+    )?;
+
+    let _creator_data_bump = accounts::check_program_data_account(
+        ctx.accounts.creator_data,
+        program_id,
+        vec![&ctx.accounts.creator.key.to_bytes(), b"User"],
+    )?;
+
+    accounts::check_system_program_key(ctx.accounts.system_program)?;
+
+    if ctx.accounts.vote_record.owner != program_id {
+        msg!("No vote recorded for this entry");
+        return Err(BlinkError::VoteNotFound.into());
+    }
+
+    let mut vote_record = VoteRecord::try_from_slice(&ctx.accounts.vote_record.data.borrow()[..])?;
+
+    if vote_record.vote == vote {
+        if vote == 0 {
+            msg!("Vote has already been retracted");
+            return Err(BlinkError::VoteAlreadyRetracted.into());
+        }
+
+        msg!("Vote is unchanged");
+        return Err(BlinkError::VoteUnchanged.into());
+    }
+
+    let mut entry = utils::read_entry(ctx.accounts.entry)?;
+    let mut creator_data = utils::read_user_data(ctx.accounts.creator_data)?;
+    let mut voter_data = utils::read_user_data(ctx.accounts.user_data)?;
+
+    // a previously retracted vote has nothing to undo
+    if vote_record.vote != 0 {
+        remove_vote(
+            vote_record.vote,
+            &mut entry,
+            &mut creator_data,
+            &mut voter_data,
+        )?;
+    }
+
+    if vote != 0 {
+        add_vote(vote, &mut entry, &mut creator_data, &mut voter_data)?;
+    }
+
+    // a retracted record is kept so that voting again goes through ChangeVote and is not
+    // rewarded twice
+    vote_record.vote = vote;
+
+    vote_record.serialize(&mut &mut ctx.accounts.vote_record.data.borrow_mut()[..])?;
+    utils::write_entry(ctx.accounts.entry, &entry)?;
+    utils::write_user_data(ctx.accounts.creator_data, ctx.accounts.user, &creator_data)?;
+    utils::write_user_data(ctx.accounts.user_data, ctx.accounts.user, &voter_data)?;
+
+    msg!("update leaderboard");
+
+    let mut leaderboard = utils::read_leaderboard(ctx.accounts.leaderboard)?;
+    let old_size = ctx.accounts.leaderboard.data_len();
+
+    let entry_score: i32 = (entry.positive_votes as i32) - (entry.negative_votes as i32);
+    utils::update_leaderboard(
+        &mut leaderboard,
+        game.leaderboard_size as usize,
+        creator_data.user_id,
+        entry_score,
+    );
+
+    utils::check_for_realloc(
+        ctx.accounts.leaderboard,
+        ctx.accounts.user,
+        old_size,
+        to_vec(&leaderboard).unwrap().len(),
+    )?;
+
+    leaderboard.serialize(&mut &mut ctx.accounts.leaderboard.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
 pub fn vote<'a>(
     program_id: &Pubkey,
     accounts: &'a [AccountInfo<'a>],
@@ -24,14 +197,23 @@ pub fn vote<'a>(
     }
 
     let clock = Clock::get()?;
-    let current_date = (clock.unix_timestamp / (24 * 60 * 60)) as u32;
+    let game = utils::load_game(ctx.accounts.game, program_id, args.game)?;
+    utils::check_voting_open(&game, args.date, clock.unix_timestamp)?;
+
+    // the reward cap is per calendar day regardless of how long the game's rounds are
+    let current_day = utils::get_day(clock.unix_timestamp);
 
     let pda_bump_seed = accounts::check_program_data_account(
         ctx.accounts.pda,
         program_id,
         vec![&accounts::PDA_SEED.to_le_bytes()],
-    )
-    .unwrap();
+    )?;
+
+    let _config_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.config,
+        program_id,
+        vec![&accounts::CONFIG_SEED.to_le_bytes()],
+    )?;
 
     let _entry_bump_seed = accounts::check_program_data_account(
         ctx.accounts.entry,
@@ -39,35 +221,47 @@ pub fn vote<'a>(
         vec![
             &ctx.accounts.creator.key.to_bytes(),
             &args.game.to_le_bytes(),
-            &current_date.to_le_bytes(),
+            &args.date.to_le_bytes(),
+        ],
+    )?;
+
+    let vote_record_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.vote_record,
+        program_id,
+        vec![
+            &ctx.accounts.user.key.to_bytes(),
+            &ctx.accounts.entry.key.to_bytes(),
+            b"Vote",
         ],
-    )
-    .unwrap();
+    )?;
+
+    let activity_bump_seed = accounts::check_program_data_account(
+        ctx.accounts.activity,
+        program_id,
+        vec![&ctx.accounts.user.key.to_bytes(), b"Activity"],
+    )?;
 
     let leaderboard_bump_seed = accounts::check_program_data_account(
         ctx.accounts.leaderboard,
         program_id,
         vec![
             &args.game.to_le_bytes(),
-            &current_date.to_le_bytes(),
+            &args.date.to_le_bytes(),
             b"Leaderboard",
         ],
-    )
-    .unwrap();
+    )?;
 
     let _user_data_bump = accounts::check_program_data_account(
         ctx.accounts.user_data,
         program_id,
         vec![&ctx.accounts.user.key.to_bytes(), b"User"],
-    )
-    .unwrap();
+    )?;
 
     let _creator_data_bump = accounts::check_program_data_account(
         ctx.accounts.creator_data,
         program_id,
         vec![&ctx.accounts.creator.key.to_bytes(), b"User"],
-    )
-    .unwrap();
+    )?;
 
     accounts::check_token_account(
         ctx.accounts.user,
@@ -78,14 +272,22 @@ pub fn vote<'a>(
 
     accounts::check_system_program_key(ctx.accounts.system_program)?;
 
+    let config = state::Config::try_from_slice(&ctx.accounts.config.data.borrow()[..])?;
+
     if ctx.accounts.creator.key == ctx.accounts.user.key {
         msg!("Creator cannot vote on their own entry");
-        return Err(ProgramError::InvalidArgument);
+        return Err(BlinkError::SelfVote.into());
     }
 
     if **ctx.accounts.entry.try_borrow_lamports()? == 0 {
         msg!("No entry for date and user");
-        return Err(ProgramError::UninitializedAccount);
+        return Err(BlinkError::EntryNotFound.into());
+    }
+
+    // anyone can send lamports to the record's address, so only a created record counts as a vote
+    if ctx.accounts.vote_record.owner == program_id {
+        msg!("User has already voted on this entry");
+        return Err(BlinkError::AlreadyVoted.into());
     }
 
     utils::create_user_data(
@@ -95,62 +297,122 @@ pub fn vote<'a>(
         program_id,
     )?;
 
-    let mut entry = state::Entry::try_from_slice(&ctx.accounts.entry.data.borrow()[..])?;
-    let mut creator_data =
-        state::User::try_from_slice(&ctx.accounts.creator_data.data.borrow()[..])?;
-    let mut voter_data = state::User::try_from_slice(&ctx.accounts.user_data.data.borrow()[..])?;
+    let mut entry = utils::read_entry(ctx.accounts.entry)?;
+    let mut creator_data = utils::read_user_data(ctx.accounts.creator_data)?;
+    let mut voter_data = utils::read_user_data(ctx.accounts.user_data)?;
 
     msg!("have initial data");
 
-    if args.vote == 1 {
-        entry.positive_votes += 1;
-        creator_data.total_positive_votes += 1;
-        voter_data.total_positive_voted += 1;
-    } else if args.vote == 2 {
-        entry.negative_votes += 1;
-        creator_data.total_negative_votes += 1;
-        voter_data.total_negative_voted += 1;
+    add_vote(args.vote, &mut entry, &mut creator_data, &mut voter_data)?;
+
+    let vote_record: VoteRecord = VoteRecord {
+        account_type: state::AccountType::VoteRecord,
+        vote: args.vote,
+    };
+
+    let vote_record_seeds: Vec<&[u8]> = vec![
+        ctx.accounts.user.key.as_ref(),
+        ctx.accounts.entry.key.as_ref(),
+        b"Vote",
+    ];
+
+    if **ctx.accounts.vote_record.try_borrow_lamports()? > 0 {
+        utils::create_funded_program_account(
+            ctx.accounts.user,
+            ctx.accounts.vote_record,
+            program_id,
+            vote_record_bump_seed,
+            to_vec(&vote_record).unwrap().len(),
+            vote_record_seeds,
+        )?;
     } else {
-        return Err(ProgramError::InvalidArgument);
+        utils::create_program_account(
+            ctx.accounts.user,
+            ctx.accounts.vote_record,
+            program_id,
+            vote_record_bump_seed,
+            to_vec(&vote_record).unwrap().len(),
+            vote_record_seeds,
+        )?;
     }
 
-    entry.serialize(&mut &mut ctx.accounts.entry.data.borrow_mut()[..])?;
-    creator_data.serialize(&mut &mut ctx.accounts.creator_data.data.borrow_mut()[..])?;
-    voter_data.serialize(&mut &mut ctx.accounts.user_data.data.borrow_mut()[..])?;
+    vote_record.serialize(&mut &mut ctx.accounts.vote_record.data.borrow_mut()[..])?;
 
-    msg!("mint reward");
+    utils::write_entry(ctx.accounts.entry, &entry)?;
+    utils::write_user_data(ctx.accounts.creator_data, ctx.accounts.user, &creator_data)?;
+    utils::write_user_data(ctx.accounts.user_data, ctx.accounts.user, &voter_data)?;
 
-    utils::create_ata(
-        ctx.accounts.user,
-        ctx.accounts.user,
-        ctx.accounts.bash_mint,
-        ctx.accounts.user_token,
-        ctx.accounts.token_2022,
-    )?;
+    if **ctx.accounts.activity.try_borrow_lamports()? == 0 {
+        let temp: Activity = Activity {
+            account_type: state::AccountType::Activity,
+            date: current_day,
+            votes: 0,
+        };
 
-    // mint the token to the user
-    let mint_to_idx = spl_token_2022::instruction::mint_to_checked(
-        ctx.accounts.token_2022.key,
-        ctx.accounts.bash_mint.key,
-        ctx.accounts.user_token.key,
-        ctx.accounts.pda.key,
-        &[ctx.accounts.pda.key],
-        10,
-        1,
-    )
-    .unwrap();
-
-    invoke_signed(
-        &mint_to_idx,
-        &[
-            ctx.accounts.token_2022.clone(),
-            ctx.accounts.bash_mint.clone(),
-            ctx.accounts.user_token.clone(),
-            ctx.accounts.user.clone(),
-            ctx.accounts.pda.clone(),
-        ],
-        &[&[&accounts::PDA_SEED.to_le_bytes(), &[pda_bump_seed]]],
-    )?;
+        utils::create_program_account(
+            ctx.accounts.user,
+            ctx.accounts.activity,
+            program_id,
+            activity_bump_seed,
+            to_vec(&temp).unwrap().len(),
+            vec![&ctx.accounts.user.key.to_bytes(), b"Activity"],
+        )?;
+
+        temp.serialize(&mut &mut ctx.accounts.activity.data.borrow_mut()[..])?;
+    }
+
+    // each user has a single activity account that starts counting again on a new day
+    let mut activity = Activity::try_from_slice(&ctx.accounts.activity.data.borrow()[..])?;
+    if activity.date != current_day {
+        activity.date = current_day;
+        activity.votes = 0;
+    }
+    activity.votes += 1;
+    activity.serialize(&mut &mut ctx.accounts.activity.data.borrow_mut()[..])?;
+
+    // the vote still counts once the cap is reached, it just isn't rewarded
+    let rewarded = activity.votes <= config.max_rewarded_votes_per_day;
+
+    if rewarded {
+        msg!("mint reward");
+
+        utils::create_ata(
+            ctx.accounts.user,
+            ctx.accounts.user,
+            ctx.accounts.bash_mint,
+            ctx.accounts.user_token,
+            ctx.accounts.token_2022,
+        )?;
+
+        // mint the token to the user
+        let mint_to_idx = spl_token_2022::instruction::mint_to_checked(
+            ctx.accounts.token_2022.key,
+            ctx.accounts.bash_mint.key,
+            ctx.accounts.user_token.key,
+            ctx.accounts.pda.key,
+            &[ctx.accounts.pda.key],
+            game.vote_reward.unwrap_or(config.vote_reward),
+            1,
+        )
+        .unwrap();
+
+        invoke_signed(
+            &mint_to_idx,
+            &[
+                ctx.accounts.token_2022.clone(),
+                ctx.accounts.bash_mint.clone(),
+                ctx.accounts.user_token.clone(),
+                ctx.accounts.user.clone(),
+                ctx.accounts.pda.clone(),
+            ],
+            &[&[&accounts::PDA_SEED.to_le_bytes(), &[pda_bump_seed]]],
+        )?;
+    } else {
+        msg!(
+            "daily vote reward limit of {} reached",
+            config.max_rewarded_votes_per_day
+        );
+    }
 
     msg!("update leaderboard");
 
@@ -158,9 +420,14 @@ pub fn vote<'a>(
         let temp: Leaderboard = Leaderboard {
             account_type: state::AccountType::Leaderboard,
             game: args.game,
-            date: current_date,
+            date: args.date,
             entrants: Vec::new(),
             scores: Vec::new(),
+            finalized: 0,
+            winners: Vec::new(),
+            prizes: Vec::new(),
+            payer: *ctx.accounts.user.key,
+            pool: 0,
         };
 
         utils::create_program_account(
@@ -171,7 +438,7 @@ pub fn vote<'a>(
             to_vec(&temp).unwrap().len(),
             vec![
                 &args.game.to_le_bytes(),
-                &current_date.to_le_bytes(),
+                &args.date.to_le_bytes(),
                 b"Leaderboard",
             ],
         )?;
@@ -181,7 +448,7 @@ pub fn vote<'a>(
     }
 
     // check if we have a reference
-    if ctx.accounts.reference.is_some() {
+    if rewarded && ctx.accounts.reference.is_some() {
         let reference = ctx.accounts.reference.unwrap();
         if reference.key != ctx.accounts.user.key {
             let ref_bash = ctx.accounts.ref_bash.unwrap();
@@ -195,7 +462,7 @@ pub fn vote<'a>(
             // only transfer if the ATA already exists
             if **ref_bash.try_borrow_lamports()? > 0 {
                 utils::mint(
-                    10,
+                    config.vote_referral_reward,
                     ctx.accounts.token_2022,
                     ctx.accounts.bash_mint,
                     ref_bash,
@@ -206,65 +473,25 @@ pub fn vote<'a>(
         }
     }
 
-    let mut leaderboard = Leaderboard::try_from_slice(&ctx.accounts.leaderboard.data.borrow()[..])?;
+    let mut leaderboard = utils::read_leaderboard(ctx.accounts.leaderboard)?;
     let old_size = ctx.accounts.leaderboard.data_len();
 
-    // check if the new score is higher than the lowest of the top 10
-    let mut min: i64 = i64::MAX;
-    let mut min_index: usize = 0;
-    let mut present: bool = false;
-    for i in 0..leaderboard.scores.len() {
-        if creator_data.user_id == leaderboard.entrants[i] {
-            present = true;
-            min_index = i;
-            break;
-        }
-        if (leaderboard.scores[i] as i64) < min {
-            min = leaderboard.scores[i] as i64;
-            min_index = i;
-        }
-    }
-
-    let entry_score: i64 = (entry.positive_votes as i64) - (entry.negative_votes as i64);
-    msg!("Have score {}", entry_score);
-    if entry_score < 0 {
-        return Ok(());
-    }
-    if present {
-        msg!("User already present in the top 10!");
-
-        leaderboard.scores[min_index] = entry_score as u32;
-        leaderboard.entrants[min_index] = creator_data.user_id;
-
-        leaderboard.serialize(&mut &mut ctx.accounts.leaderboard.data.borrow_mut()[..])?;
-    }
+    let entry_score: i32 = (entry.positive_votes as i32) - (entry.negative_votes as i32);
+    utils::update_leaderboard(
+        &mut leaderboard,
+        game.leaderboard_size as usize,
+        creator_data.user_id,
+        entry_score,
+    );
 
-    if !present && (entry_score as i64 > min || leaderboard.scores.len() < 10) {
-        msg!(
-            "New entry in top 10! {} > {} for user {}",
-            entry_score,
-            min,
-            creator_data.user_id
-        );
-
-        // if we have less than 10 then just add, otherwse replace
-        if leaderboard.scores.len() < 10 {
-            leaderboard.scores.push(entry_score as u32);
-            leaderboard.entrants.push(creator_data.user_id);
-
-            utils::check_for_realloc(
-                ctx.accounts.leaderboard,
-                ctx.accounts.user,
-                old_size,
-                to_vec(&leaderboard).unwrap().len(),
-            )?;
-        } else {
-            leaderboard.scores[min_index] = entry_score as u32;
-            leaderboard.entrants[min_index] = creator_data.user_id;
-        }
+    utils::check_for_realloc(
+        ctx.accounts.leaderboard,
+        ctx.accounts.user,
+        old_size,
+        to_vec(&leaderboard).unwrap().len(),
+    )?;
 
-        leaderboard.serialize(&mut &mut ctx.accounts.leaderboard.data.borrow_mut()[..])?;
-    }
+    leaderboard.serialize(&mut &mut ctx.accounts.leaderboard.data.borrow_mut()[..])?;
 
     Ok(())
 }
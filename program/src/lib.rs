@@ -1,5 +1,6 @@
 pub mod accounts;
 pub mod entrypoint;
+pub mod error;
 pub mod instruction;
 pub mod instructions;
 pub mod processor;
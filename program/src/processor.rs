@@ -1,4 +1,4 @@
-use crate::instructions;
+use crate::{accounts, instructions};
 use borsh::BorshDeserialize;
 
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey};
@@ -14,6 +14,21 @@ impl Processor {
     ) -> ProgramResult {
         let instruction = BlinkInstruction::try_from_slice(instruction_data)?;
 
+        // anything that mints, burns or moves items is blocked while the program is paused
+        match instruction {
+            BlinkInstruction::Enter(_)
+            | BlinkInstruction::Vote(_)
+            | BlinkInstruction::ClaimPrize(_)
+            | BlinkInstruction::DistributePrizes(_)
+            | BlinkInstruction::ClaimPrizes(_)
+            | BlinkInstruction::ListItem(_)
+            | BlinkInstruction::PurchaseItem(_)
+            | BlinkInstruction::DelistItem() => {
+                accounts::check_not_paused(program_id, accounts)?;
+            }
+            _ => {}
+        }
+
         match instruction {
             BlinkInstruction::Init() => {
                 msg!("Init");
@@ -39,6 +54,66 @@ impl Processor {
                 msg!("PurchaseItem");
                 instructions::purchase_item(program_id, accounts, args)
             }
+            BlinkInstruction::ChangeVote(args) => {
+                msg!("ChangeVote");
+                instructions::change_vote(program_id, accounts, args)
+            }
+            BlinkInstruction::RetractVote(args) => {
+                msg!("RetractVote");
+                instructions::retract_vote(program_id, accounts, args)
+            }
+            BlinkInstruction::UpdateConfig(args) => {
+                msg!("UpdateConfig");
+                instructions::update_config(program_id, accounts, args)
+            }
+            BlinkInstruction::SetPaused(args) => {
+                msg!("SetPaused");
+                instructions::set_paused(program_id, accounts, args)
+            }
+            BlinkInstruction::TransferAdmin(args) => {
+                msg!("TransferAdmin");
+                instructions::transfer_admin(program_id, accounts, args)
+            }
+            BlinkInstruction::AcceptAdmin() => {
+                msg!("AcceptAdmin");
+                instructions::accept_admin(program_id, accounts)
+            }
+            BlinkInstruction::SetProfile(args) => {
+                msg!("SetProfile");
+                instructions::set_profile(program_id, accounts, args)
+            }
+            BlinkInstruction::UpdateGame(args) => {
+                msg!("UpdateGame");
+                instructions::update_game(program_id, accounts, args)
+            }
+            BlinkInstruction::RegisterGame(args) => {
+                msg!("RegisterGame");
+                instructions::register_game(program_id, accounts, args)
+            }
+            BlinkInstruction::FinalizeRound(args) => {
+                msg!("FinalizeRound");
+                instructions::finalize_round(program_id, accounts, args)
+            }
+            BlinkInstruction::DistributePrizes(args) => {
+                msg!("DistributePrizes");
+                instructions::distribute_prizes(program_id, accounts, args)
+            }
+            BlinkInstruction::ClaimPrizes(args) => {
+                msg!("ClaimPrizes");
+                instructions::claim_prizes(program_id, accounts, args)
+            }
+            BlinkInstruction::CloseEntry(args) => {
+                msg!("CloseEntry");
+                instructions::close_entry(program_id, accounts, args)
+            }
+            BlinkInstruction::CloseLeaderboard(args) => {
+                msg!("CloseLeaderboard");
+                instructions::close_leaderboard(program_id, accounts, args)
+            }
+            BlinkInstruction::DelistItem() => {
+                msg!("DelistItem");
+                instructions::delist_item(program_id, accounts)
+            }
         }
     }
 }
@@ -3,6 +3,14 @@ use std::collections::HashMap;
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::pubkey::Pubkey;
 
+pub const MAX_URI_LENGTH: usize = 200;
+pub const MAX_TWITTER_LENGTH: usize = 15;
+pub const MAX_DISPLAY_NAME_LENGTH: usize = 32;
+pub const MAX_LEADERBOARD_SIZE: u8 = 100;
+pub const MAX_GAME_NAME_LENGTH: usize = 32;
+// FinalizeRound takes a user account for every paid rank in a single transaction
+pub const MAX_PRIZE_RANKS: usize = 20;
+
 #[derive(Default, BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
 pub enum AccountType {
     #[default]
@@ -11,6 +19,12 @@ pub enum AccountType {
     Entry,
     Leaderboard,
     Listing,
+    VoteRecord,
+    Config,
+    Handle,
+    Activity,
+    Game,
+    Archive,
 }
 pub struct TokenDetails {
     pub name: String,
@@ -32,6 +46,23 @@ pub struct ProgramStats {
     pub num_users: u32,
 }
 
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct Config {
+    pub account_type: AccountType,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub paused: u8,
+    pub entry_reward: u64,
+    pub entry_referral_reward: u64,
+    pub vote_reward: u64,
+    pub vote_referral_reward: u64,
+    pub max_rewarded_votes_per_day: u32,
+    // seconds after voting closes before unclaimed entries and leaderboards can be closed
+    pub retention_period: u32,
+    // share of peer to peer sales paid to the fees account, in basis points
+    pub market_fee_bps: u16,
+}
+
 #[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
 pub struct User {
     pub account_type: AccountType,
@@ -43,6 +74,49 @@ pub struct User {
     pub total_negative_votes: u32,
     pub total_positive_voted: u32,
     pub total_negative_voted: u32,
+    pub display_name: String,
+    pub avatar_uri: String,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub enum PrizeTable {
+    // amount paid to each rank, starting with first place
+    Fixed(Vec<u64>),
+    // share of the pool paid to each rank in basis points, starting with first place
+    Pool { amount: u64, shares: Vec<u16> },
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct Game {
+    pub account_type: AccountType,
+    pub game: u8,
+    pub name: String,
+    pub description_uri: String,
+    pub active: u8,
+    // override the config rewards for this game when set
+    pub entry_reward: Option<u64>,
+    pub vote_reward: Option<u64>,
+    // $BASH paid into the round's prize pool by each entrant, zero for free games.  the rake in
+    // basis points is taken from the pool when the round is finalized
+    pub entry_fee: u64,
+    pub rake_bps: u16,
+    // unix timestamps, an end_time of zero means the game never ends
+    pub start_time: i64,
+    pub end_time: i64,
+    // rounds last round_length seconds, starting round_offset seconds after each multiple of
+    // round_length since the unix epoch
+    pub round_length: u32,
+    pub round_offset: u32,
+    // entries are accepted for submission_length seconds from the start of a round, voting on
+    // the round opens voting_delay seconds after it starts and stays open for voting_length
+    // seconds, so the two phases may overlap and voting may run on after the round has ended
+    pub submission_length: u32,
+    pub voting_delay: u32,
+    pub voting_length: u32,
+    pub leaderboard_size: u8,
+    pub prizes: PrizeTable,
+    // when set, entrants on the same score share the prizes for the ranks they occupy equally
+    pub split_ties: u8,
 }
 
 #[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
@@ -51,7 +125,16 @@ pub struct Leaderboard {
     pub game: u8,
     pub date: u32,
     pub entrants: Vec<u32>,
-    pub scores: Vec<u32>,
+    pub scores: Vec<i32>,
+    // set by FinalizeRound, after which winners holds the paid ranks in order and prizes the
+    // amount each of them is owed
+    pub finalized: u8,
+    pub winners: Vec<Pubkey>,
+    pub prizes: Vec<u64>,
+    // the account that paid for the leaderboard and gets the rent back when it is closed
+    pub payer: Pubkey,
+    // entry fees held by the round's pool
+    pub pool: u64,
 }
 
 #[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
@@ -60,6 +143,8 @@ pub struct Entry {
     pub positive_votes: u32,
     pub negative_votes: u32,
     pub reward_claimed: u8,
+    pub uri: String,
+    pub content_hash: [u8; 32],
 }
 
 #[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
@@ -69,5 +154,40 @@ pub struct Listing {
     pub item_address: Pubkey,
     pub price: u64,
     pub quantity: u64,
+    // tokens are sold in bundles of this many base units, with the price set per bundle.
+    // 0 for listings made before bundles, which are priced per whole token
     pub bundle_size: u64,
+    // only the seller or the admin can change or withdraw the listing
+    pub seller: Pubkey,
+    // 0 burns the buyer's $BASH, 1 pays it to the seller less the market fee
+    pub payment: u8,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct VoteRecord {
+    pub account_type: AccountType,
+    pub vote: u8,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct Handle {
+    pub account_type: AccountType,
+    pub user_key: Pubkey,
+}
+
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct Activity {
+    pub account_type: AccountType,
+    pub date: u32,
+    pub votes: u32,
+}
+
+// the prize winners of a closed leaderboard
+#[derive(Clone, BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+pub struct Archive {
+    pub account_type: AccountType,
+    pub game: u8,
+    pub date: u32,
+    pub winners: Vec<Pubkey>,
+    pub prizes: Vec<u64>,
 }
@@ -20,6 +20,7 @@ use std::mem;
 
 use crate::{
     accounts,
+    error::BlinkError,
     state::{self, User},
 };
 pub fn to_sol(value: u64) -> f64 {
@@ -89,6 +90,45 @@ pub fn create_program_account<'a>(
     Ok(())
 }
 
+// a pda that was sent lamports before it was created is still owned by the system program and
+// can't be created with create_account, so top it up to rent exemption and take it over instead
+pub fn create_funded_program_account<'a>(
+    funding_account: &AccountInfo<'a>,
+    pda: &AccountInfo<'a>,
+    program_id: &Pubkey,
+    bump_seed: u8,
+    data_size: usize,
+    seed: Vec<&[u8]>,
+) -> ProgramResult {
+    let lamports = rent::Rent::default().minimum_balance(data_size);
+    let current = **pda.try_borrow_lamports()?;
+
+    if current < lamports {
+        invoke(
+            &system_instruction::transfer(funding_account.key, pda.key, lamports - current),
+            &[funding_account.clone(), pda.clone()],
+        )?;
+    }
+
+    let bump = [bump_seed];
+    let mut signer_seeds = seed;
+    signer_seeds.push(&bump);
+
+    invoke_signed(
+        &system_instruction::allocate(pda.key, data_size.try_into().unwrap()),
+        std::slice::from_ref(pda),
+        &[&signer_seeds],
+    )?;
+
+    invoke_signed(
+        &system_instruction::assign(pda.key, program_id),
+        std::slice::from_ref(pda),
+        &[&signer_seeds],
+    )?;
+
+    Ok(())
+}
+
 pub fn get_token_balance<'a>(token_source_account: &AccountInfo<'a>) -> u64 {
     let base_data = &token_source_account.try_borrow_data().unwrap();
     let account_state = StateWithExtensions::<Account>::unpack(base_data).unwrap();
@@ -493,6 +533,11 @@ pub fn check_for_realloc<'a>(
         data_account.realloc(new_size, false)?;
     }
 
+    // shrink the data if entries were removed so that the account still deserializes
+    if new_size < old_size {
+        data_account.realloc(new_size, false)?;
+    }
+
     Ok(())
 }
 
@@ -559,6 +604,210 @@ pub fn mint<'a>(
     Ok(())
 }
 
+// reads a registered game whether or not it is still active
+pub fn read_game<'a>(
+    game_account: &'a AccountInfo<'a>,
+    program_id: &Pubkey,
+    game: u8,
+) -> Result<state::Game, ProgramError> {
+    let _game_bump_seed = accounts::check_program_data_account(
+        game_account,
+        program_id,
+        vec![&game.to_le_bytes(), b"Game"],
+    )?;
+
+    if **game_account.try_borrow_lamports()? == 0 {
+        msg!("game {} has not been registered", game);
+        return Err(BlinkError::GameNotFound.into());
+    }
+
+    let game_data = state::Game::try_from_slice(&game_account.data.borrow()[..])?;
+    Ok(game_data)
+}
+
+// every game must be registered with RegisterGame before it can be played
+pub fn load_game<'a>(
+    game_account: &'a AccountInfo<'a>,
+    program_id: &Pubkey,
+    game: u8,
+) -> Result<state::Game, ProgramError> {
+    let game_data = read_game(game_account, program_id, game)?;
+
+    if game_data.active == 0 {
+        msg!("game {} is not active", game);
+        return Err(BlinkError::GameInactive.into());
+    }
+
+    Ok(game_data)
+}
+
+// entries and votes are only accepted between the game's start and end times
+pub fn check_game_open(game: &state::Game, unix_timestamp: i64) -> ProgramResult {
+    if unix_timestamp < game.start_time || (game.end_time != 0 && unix_timestamp >= game.end_time) {
+        msg!(
+            "game {} is open from {} to {}",
+            game.game,
+            game.start_time,
+            game.end_time
+        );
+        return Err(BlinkError::GameNotOpen.into());
+    }
+
+    Ok(())
+}
+
+// handles are case insensitive and may be given with a leading '@', the normalized form is
+// what gets stored on the user and used as the seed for the handle account
+pub fn normalize_handle(handle: &str) -> Result<String, ProgramError> {
+    let normalized = handle.trim_start_matches('@').to_ascii_lowercase();
+
+    if !normalized
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        msg!("invalid handle {}", handle);
+        return Err(BlinkError::InvalidHandle.into());
+    }
+
+    Ok(normalized)
+}
+
+pub fn close_program_account<'a>(
+    account: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+) -> ProgramResult {
+    let account_lamports = **account.try_borrow_lamports()?;
+
+    **account.try_borrow_mut_lamports()? -= account_lamports;
+    **destination.try_borrow_mut_lamports()? += account_lamports;
+
+    account.realloc(0, false)?;
+    account.assign(&solana_program::system_program::ID);
+
+    Ok(())
+}
+
+// user accounts created before profiles were added end after the vote totals, and are read
+// as having an empty display name and avatar
+pub fn read_user_data(user_data: &AccountInfo) -> Result<User, ProgramError> {
+    let data = user_data.data.borrow();
+
+    if let Ok(user) = User::try_from_slice(&data[..]) {
+        return Ok(user);
+    }
+
+    let mut upgraded = data.to_vec();
+    upgraded.extend_from_slice(&to_vec(&"".to_string()).unwrap());
+    upgraded.extend_from_slice(&to_vec(&"".to_string()).unwrap());
+
+    Ok(User::try_from_slice(&upgraded)?)
+}
+
+// entries created before content was stored end after the claim flag, and are read as having
+// an empty uri and hash
+pub fn read_entry(entry: &AccountInfo) -> Result<state::Entry, ProgramError> {
+    let data = entry.data.borrow();
+
+    if let Ok(entry) = state::Entry::try_from_slice(&data[..]) {
+        return Ok(entry);
+    }
+
+    let mut upgraded = data.to_vec();
+    upgraded.extend_from_slice(&to_vec(&"".to_string()).unwrap());
+    upgraded.extend_from_slice(&[0; 32]);
+
+    Ok(state::Entry::try_from_slice(&upgraded)?)
+}
+
+// only the votes and claim flag ever change, so legacy entries keep their shorter layout
+pub fn write_entry(entry_account: &AccountInfo, entry: &state::Entry) -> ProgramResult {
+    let data = to_vec(entry).unwrap();
+    let len = data.len().min(entry_account.data_len());
+
+    entry_account.data.borrow_mut()[..len].copy_from_slice(&data[..len]);
+
+    Ok(())
+}
+
+// leaderboards created before rounds were finalized end after the scores, and are read as
+// unfinalized with no pool.  nobody was recorded as paying for them, so their rent goes to
+// the admin when they are closed
+pub fn read_leaderboard(leaderboard: &AccountInfo) -> Result<state::Leaderboard, ProgramError> {
+    let data = leaderboard.data.borrow();
+
+    if let Ok(leaderboard) = state::Leaderboard::try_from_slice(&data[..]) {
+        return Ok(leaderboard);
+    }
+
+    let mut upgraded = data.to_vec();
+    upgraded.push(0);
+    upgraded.extend_from_slice(&to_vec(&Vec::<Pubkey>::new()).unwrap());
+    upgraded.extend_from_slice(&to_vec(&Vec::<u64>::new()).unwrap());
+    upgraded.extend_from_slice(&Pubkey::default().to_bytes());
+    upgraded.extend_from_slice(&0_u64.to_le_bytes());
+
+    Ok(state::Leaderboard::try_from_slice(&upgraded)?)
+}
+
+// grows legacy accounts to fit the current layout before writing them
+pub fn write_user_data<'a>(
+    user_data: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    user: &User,
+) -> ProgramResult {
+    let new_size = to_vec(user).unwrap().len();
+
+    if user_data.data_len() != new_size {
+        check_for_realloc(user_data, payer, user_data.data_len(), new_size)?;
+    }
+
+    user.serialize(&mut &mut user_data.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// listings created before sellers were recorded end after the bundle size.  they are read as
+// belonging to the admin and burning the payment, and token listings keep their price per
+// whole token
+pub fn read_listing(
+    listing: &AccountInfo,
+    config: &state::Config,
+) -> Result<state::Listing, ProgramError> {
+    let data = listing.data.borrow();
+
+    if let Ok(listing) = state::Listing::try_from_slice(&data[..]) {
+        return Ok(listing);
+    }
+
+    let mut upgraded = data.to_vec();
+    upgraded.extend_from_slice(&config.admin.to_bytes());
+    upgraded.push(0);
+
+    let mut listing = state::Listing::try_from_slice(&upgraded)?;
+    if listing.item_type == 1 {
+        listing.bundle_size = 0;
+    }
+
+    Ok(listing)
+}
+
+// grows legacy listings to fit the current layout before writing them
+pub fn write_listing<'a>(
+    listing_account: &AccountInfo<'a>,
+    payer: &AccountInfo<'a>,
+    listing: &state::Listing,
+) -> ProgramResult {
+    let new_size = to_vec(listing).unwrap().len();
+
+    if listing_account.data_len() != new_size {
+        check_for_realloc(listing_account, payer, listing_account.data_len(), new_size)?;
+    }
+
+    listing.serialize(&mut &mut listing_account.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
 pub fn create_user_data<'a>(
     user: &'a AccountInfo<'a>,
     user_data: &'a AccountInfo<'a>,
@@ -569,11 +818,12 @@ pub fn create_user_data<'a>(
         user_data,
         program_id,
         vec![&user.key.to_bytes(), b"User"],
-    )
-    .unwrap();
+    )?;
 
+    // bring accounts created before profiles existed up to the current layout
     if **user_data.try_borrow_lamports()? != 0 {
-        return Ok(());
+        let temp = read_user_data(user_data)?;
+        return write_user_data(user_data, user, &temp);
     }
 
     let mut program_stats = state::ProgramStats::try_from_slice(&data.data.borrow()[..])?;
@@ -589,6 +839,8 @@ pub fn create_user_data<'a>(
         total_negative_votes: 0,
         total_positive_voted: 0,
         total_negative_voted: 0,
+        display_name: "".to_string(),
+        avatar_uri: "".to_string(),
     };
 
     create_program_account(
@@ -0,0 +1,392 @@
+use solana_program::msg;
+
+use crate::state::{Leaderboard, PrizeTable};
+
+// the original fixed leaderboard size, a sensible default when registering a game
+pub const LEADERBOARD_SIZE: usize = 10;
+
+// keeps the top entrants in sync with the latest score for an entry, ordered from highest to
+// lowest.  entrants that are already present always take their new score, even if it has
+// dropped below zero, and are moved to their new position.  new entrants either fill an empty
+// slot or evict the current lowest score if they beat it.
+// ties are broken by whoever reached the score first: an updated entrant is always placed
+// behind everyone already on the same score, and of the tied lowest scores the most recent
+// to arrive is the one evicted
+pub fn update_leaderboard(
+    leaderboard: &mut Leaderboard,
+    leaderboard_size: usize,
+    user_id: u32,
+    entry_score: i32,
+) {
+    msg!("Have score {}", entry_score);
+
+    let present = leaderboard.entrants.iter().position(|&id| id == user_id);
+
+    match present {
+        Some(index) => {
+            msg!("User already present in the leaderboard!");
+            leaderboard.entrants.remove(index);
+            leaderboard.scores.remove(index);
+        }
+        None => {
+            if leaderboard.scores.len() >= leaderboard_size {
+                let mut min_index: usize = 0;
+                for i in 1..leaderboard.scores.len() {
+                    if leaderboard.scores[i] <= leaderboard.scores[min_index] {
+                        min_index = i;
+                    }
+                }
+
+                if entry_score <= leaderboard.scores[min_index] {
+                    return;
+                }
+
+                msg!(
+                    "New entry in leaderboard! {} > {} for user {}",
+                    entry_score,
+                    leaderboard.scores[min_index],
+                    user_id
+                );
+
+                leaderboard.entrants.remove(min_index);
+                leaderboard.scores.remove(min_index);
+            }
+        }
+    }
+
+    // insert after everyone on the same or a higher score
+    let position = leaderboard
+        .scores
+        .iter()
+        .position(|&score| score < entry_score)
+        .unwrap_or(leaderboard.scores.len());
+
+    leaderboard.entrants.insert(position, user_id);
+    leaderboard.scores.insert(position, entry_score);
+}
+
+pub fn get_prize(prizes: &PrizeTable, rank: usize) -> u64 {
+    match prizes {
+        PrizeTable::Fixed(amounts) => amounts.get(rank).copied().unwrap_or(0),
+        PrizeTable::Pool { amount, shares } => match shares.get(rank) {
+            Some(&share) => ((*amount as u128) * (share as u128) / 10000) as u64,
+            None => 0,
+        },
+    }
+}
+
+// entrants ordered from highest to lowest score.  the sort is stable so entrants on the same
+// score keep their leaderboard order, i.e. whoever reached the score first ranks higher
+pub fn rank_entrants(leaderboard: &Leaderboard) -> Vec<(u32, i32)> {
+    let mut ranked: Vec<(u32, i32)> = leaderboard
+        .entrants
+        .iter()
+        .copied()
+        .zip(leaderboard.scores.iter().copied())
+        .collect();
+
+    ranked.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+
+    ranked
+}
+
+// the prize for the entrant at `rank` in the output of `rank_entrants`.  when split_ties is
+// set the prizes for every rank shared by the tied entrants are pooled and split equally, any
+// remainder from the division is not paid out
+pub fn get_ranked_prize(
+    prizes: &PrizeTable,
+    ranked: &[(u32, i32)],
+    rank: usize,
+    split_ties: bool,
+) -> u64 {
+    if !split_ties || rank >= ranked.len() {
+        return get_prize(prizes, rank);
+    }
+
+    let score = ranked[rank].1;
+    let first = ranked.iter().position(|&(_, s)| s == score).unwrap();
+    let last = ranked.iter().rposition(|&(_, s)| s == score).unwrap();
+
+    let pooled: u64 = (first..=last).map(|r| get_prize(prizes, r)).sum();
+
+    pooled / (last - first + 1) as u64
+}
+
+// the prize for each rank of a finalized round and the amount left for the fees account.
+// rounds without a pool pay the table as it stands.  rounds with a pool pay from what is left
+// once the rake has been taken, with pool tables sharing it out and fixed tables, for games
+// switched away from an entry fee, paid in rank order until the pool runs out
+pub fn get_round_payouts(
+    prizes: &PrizeTable,
+    ranked: &[(u32, i32)],
+    split_ties: bool,
+    pool: u64,
+    rake_bps: u16,
+) -> (Vec<u64>, u64) {
+    if pool == 0 {
+        let payouts = (0..ranked.len())
+            .map(|rank| get_ranked_prize(prizes, ranked, rank, split_ties))
+            .collect();
+        return (payouts, 0);
+    }
+
+    let rake = ((pool as u128) * (rake_bps.min(10000) as u128) / 10000) as u64;
+    let mut remaining = pool - rake;
+
+    let prizes = match prizes {
+        PrizeTable::Pool { amount: _, shares } => PrizeTable::Pool {
+            amount: remaining,
+            shares: shares.clone(),
+        },
+        PrizeTable::Fixed(amounts) => PrizeTable::Fixed(amounts.clone()),
+    };
+
+    let payouts = (0..ranked.len())
+        .map(|rank| {
+            let prize = get_ranked_prize(&prizes, ranked, rank, split_ties).min(remaining);
+            remaining -= prize;
+            prize
+        })
+        .collect();
+
+    (payouts, remaining + rake)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::AccountType;
+    use solana_program::pubkey::Pubkey;
+
+    fn leaderboard(entrants: Vec<u32>, scores: Vec<i32>) -> Leaderboard {
+        Leaderboard {
+            account_type: AccountType::Leaderboard,
+            game: 0,
+            date: 0,
+            entrants,
+            scores,
+            finalized: 0,
+            winners: Vec::new(),
+            prizes: Vec::new(),
+            payer: Pubkey::default(),
+            pool: 0,
+        }
+    }
+
+    fn full_leaderboard() -> Leaderboard {
+        leaderboard((1..=10).collect(), vec![10, 9, 8, 7, 6, 5, 4, 3, 2, 1])
+    }
+
+    #[test]
+    fn new_entrants_fill_empty_slots() {
+        let mut board = leaderboard(Vec::new(), Vec::new());
+
+        update_leaderboard(&mut board, LEADERBOARD_SIZE, 1, 0);
+        update_leaderboard(&mut board, LEADERBOARD_SIZE, 2, 3);
+        update_leaderboard(&mut board, LEADERBOARD_SIZE, 3, -2);
+
+        assert_eq!(board.entrants, vec![2, 1, 3]);
+        assert_eq!(board.scores, vec![3, 0, -2]);
+    }
+
+    #[test]
+    fn present_entrant_is_moved_up() {
+        let mut board = leaderboard(vec![1, 2, 3], vec![5, 3, 1]);
+
+        update_leaderboard(&mut board, LEADERBOARD_SIZE, 3, 6);
+
+        assert_eq!(board.entrants, vec![3, 1, 2]);
+        assert_eq!(board.scores, vec![6, 5, 3]);
+    }
+
+    #[test]
+    fn present_entrant_is_demoted_below_zero() {
+        let mut board = leaderboard(vec![1, 2, 3], vec![5, 3, 1]);
+
+        update_leaderboard(&mut board, LEADERBOARD_SIZE, 1, -1);
+
+        assert_eq!(board.entrants, vec![2, 3, 1]);
+        assert_eq!(board.scores, vec![3, 1, -1]);
+    }
+
+    #[test]
+    fn full_leaderboard_evicts_lowest_score() {
+        let mut board = full_leaderboard();
+
+        update_leaderboard(&mut board, LEADERBOARD_SIZE, 11, 4);
+
+        assert_eq!(board.entrants.len(), LEADERBOARD_SIZE);
+        assert!(!board.entrants.contains(&10));
+        assert_eq!(board.entrants[6..8], [7, 11]);
+        assert_eq!(board.scores[6..8], [4, 4]);
+    }
+
+    #[test]
+    fn full_leaderboard_ignores_score_not_above_lowest() {
+        let mut board = full_leaderboard();
+
+        update_leaderboard(&mut board, LEADERBOARD_SIZE, 11, 1);
+        update_leaderboard(&mut board, LEADERBOARD_SIZE, 12, -3);
+
+        assert_eq!(board, full_leaderboard());
+    }
+
+    #[test]
+    fn negative_entrant_is_evicted_by_new_entry() {
+        let mut board = full_leaderboard();
+        update_leaderboard(&mut board, LEADERBOARD_SIZE, 10, -2);
+
+        update_leaderboard(&mut board, LEADERBOARD_SIZE, 11, 0);
+
+        assert_eq!(board.entrants.len(), LEADERBOARD_SIZE);
+        assert!(!board.entrants.contains(&10));
+        assert_eq!(board.entrants[9], 11);
+        assert_eq!(board.scores[9], 0);
+    }
+
+    #[test]
+    fn full_leaderboard_updates_present_entrant_in_place() {
+        let mut board = full_leaderboard();
+
+        update_leaderboard(&mut board, LEADERBOARD_SIZE, 1, -5);
+
+        assert_eq!(board.entrants.len(), LEADERBOARD_SIZE);
+        assert_eq!(board.entrants[9], 1);
+        assert_eq!(board.scores[9], -5);
+        assert_eq!(board.entrants[0], 2);
+    }
+
+    #[test]
+    fn smaller_leaderboard_evicts_sooner() {
+        let mut board = leaderboard(vec![1, 2, 3], vec![5, 3, 1]);
+
+        update_leaderboard(&mut board, 3, 4, 2);
+
+        assert_eq!(board.entrants, vec![1, 2, 4]);
+        assert_eq!(board.scores, vec![5, 3, 2]);
+    }
+
+    #[test]
+    fn prizes_by_rank() {
+        let fixed = PrizeTable::Fixed(vec![5000, 2500, 1000]);
+        assert_eq!(get_prize(&fixed, 0), 5000);
+        assert_eq!(get_prize(&fixed, 2), 1000);
+        assert_eq!(get_prize(&fixed, 3), 0);
+
+        let pool = PrizeTable::Pool {
+            amount: 1001,
+            shares: vec![5000, 3000, 2000],
+        };
+        assert_eq!(get_prize(&pool, 0), 500);
+        assert_eq!(get_prize(&pool, 1), 300);
+        assert_eq!(get_prize(&pool, 2), 200);
+        assert_eq!(get_prize(&pool, 3), 0);
+    }
+
+    #[test]
+    fn ties_rank_whoever_reached_the_score_first() {
+        let mut board = leaderboard(Vec::new(), Vec::new());
+
+        update_leaderboard(&mut board, LEADERBOARD_SIZE, 1, 1);
+        update_leaderboard(&mut board, LEADERBOARD_SIZE, 2, 2);
+        update_leaderboard(&mut board, LEADERBOARD_SIZE, 1, 2);
+        update_leaderboard(&mut board, LEADERBOARD_SIZE, 3, 2);
+
+        let ranked = rank_entrants(&board);
+        assert_eq!(ranked, vec![(2, 2), (1, 2), (3, 2)]);
+    }
+
+    #[test]
+    fn unsorted_board_is_ranked_stably() {
+        let board = leaderboard(vec![1, 2, 3, 4], vec![1, 3, 1, 3]);
+
+        assert_eq!(rank_entrants(&board), vec![(2, 3), (4, 3), (1, 1), (3, 1)]);
+    }
+
+    #[test]
+    fn tied_entrants_split_prizes_equally() {
+        let fixed = PrizeTable::Fixed(vec![5000, 2500, 1000]);
+        let board = leaderboard(vec![1, 2, 3, 4, 5], vec![9, 7, 7, 7, 3]);
+        let ranked = rank_entrants(&board);
+
+        let amounts: Vec<u64> = (0..ranked.len())
+            .map(|rank| get_ranked_prize(&fixed, &ranked, rank, true))
+            .collect();
+
+        assert_eq!(amounts, vec![5000, 1166, 1166, 1166, 0]);
+    }
+
+    #[test]
+    fn every_tied_entrant_gets_the_same_prize() {
+        let pool = PrizeTable::Pool {
+            amount: 10000,
+            shares: vec![4000, 3000, 2000, 1000],
+        };
+        let board = leaderboard(vec![1, 2, 3, 4, 5, 6], vec![5, 5, 4, 4, 4, 4]);
+        let ranked = rank_entrants(&board);
+
+        for rank in 0..ranked.len() {
+            for other in 0..ranked.len() {
+                if ranked[rank].1 == ranked[other].1 {
+                    assert_eq!(
+                        get_ranked_prize(&pool, &ranked, rank, true),
+                        get_ranked_prize(&pool, &ranked, other, true)
+                    );
+                }
+            }
+        }
+
+        assert_eq!(get_ranked_prize(&pool, &ranked, 0, true), 3500);
+        assert_eq!(get_ranked_prize(&pool, &ranked, 5, true), 750);
+    }
+
+    #[test]
+    fn ties_are_not_split_by_default() {
+        let fixed = PrizeTable::Fixed(vec![5000, 2500, 1000]);
+        let board = leaderboard(vec![1, 2], vec![4, 4]);
+        let ranked = rank_entrants(&board);
+
+        assert_eq!(get_ranked_prize(&fixed, &ranked, 0, false), 5000);
+        assert_eq!(get_ranked_prize(&fixed, &ranked, 1, false), 2500);
+    }
+
+    #[test]
+    fn fixed_prizes_without_a_pool_pay_no_fees() {
+        let board = leaderboard(vec![1, 2, 3], vec![5, 3, 1]);
+        let ranked = rank_entrants(&board);
+        let prizes = PrizeTable::Fixed(vec![5000, 2500, 1000]);
+
+        assert_eq!(
+            get_round_payouts(&prizes, &ranked, false, 0, 500),
+            (vec![5000, 2500, 1000], 0)
+        );
+    }
+
+    #[test]
+    fn pool_prizes_pay_the_rake_and_remainder_as_fees() {
+        let board = leaderboard(vec![1, 2, 3], vec![5, 3, 1]);
+        let ranked = rank_entrants(&board);
+        let prizes = PrizeTable::Pool {
+            amount: 0,
+            shares: vec![6000, 3000],
+        };
+
+        // a 10% rake leaves 900 to share, and the 10% the shares don't cover goes to fees
+        assert_eq!(
+            get_round_payouts(&prizes, &ranked, false, 1000, 1000),
+            (vec![540, 270, 0], 190)
+        );
+    }
+
+    #[test]
+    fn fixed_prizes_are_capped_by_the_pool() {
+        let board = leaderboard(vec![1, 2, 3], vec![5, 3, 1]);
+        let ranked = rank_entrants(&board);
+        let prizes = PrizeTable::Fixed(vec![500, 400, 300]);
+
+        assert_eq!(
+            get_round_payouts(&prizes, &ranked, false, 1000, 0),
+            (vec![500, 400, 100], 0)
+        );
+    }
+}
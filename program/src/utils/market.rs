@@ -0,0 +1,67 @@
+use solana_program::program_error::ProgramError;
+
+use crate::error::BlinkError;
+
+// listings are sold in whole bundles, with the price set per bundle.  a bundle of 10^decimals
+// base units prices the listing per token
+pub fn get_bundle_price(price: u64, quantity: u64, bundle_size: u64) -> Result<u64, ProgramError> {
+    if quantity.checked_rem(bundle_size) != Some(0) {
+        return Err(BlinkError::PartialBundle.into());
+    }
+
+    price
+        .checked_mul(quantity / bundle_size)
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+pub fn get_default_bundle_size(decimals: u8) -> Result<u64, ProgramError> {
+    10_u64
+        .checked_pow(decimals as u32)
+        .ok_or(ProgramError::ArithmeticOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn whole_bundles_are_exact() {
+        assert_eq!(get_bundle_price(250, 3_000_000, 1_000_000), Ok(750));
+        assert_eq!(get_bundle_price(250, 0, 1_000_000), Ok(0));
+        assert_eq!(get_bundle_price(7, 5, 1), Ok(35));
+        // packs of five tokens with no decimals
+        assert_eq!(get_bundle_price(40, 15, 5), Ok(120));
+    }
+
+    #[test]
+    fn partial_bundles_are_rejected() {
+        assert_eq!(
+            get_bundle_price(3, 500_000, 1_000_000),
+            Err(BlinkError::PartialBundle.into())
+        );
+        assert_eq!(
+            get_bundle_price(40, 7, 5),
+            Err(BlinkError::PartialBundle.into())
+        );
+        assert_eq!(
+            get_bundle_price(40, 5, 0),
+            Err(BlinkError::PartialBundle.into())
+        );
+    }
+
+    #[test]
+    fn large_quantities_do_not_lose_precision() {
+        // f64 can't represent this product exactly
+        let quantity = 9_007_199_254_740_993;
+        assert_eq!(get_bundle_price(1, quantity, 1), Ok(quantity));
+        assert_eq!(
+            get_bundle_price(u64::MAX, 2, 1),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+        assert_eq!(get_default_bundle_size(6), Ok(1_000_000));
+        assert_eq!(
+            get_default_bundle_size(20),
+            Err(ProgramError::ArithmeticOverflow)
+        );
+    }
+}
@@ -1,7 +1,15 @@
 pub mod common;
 pub mod core;
+pub mod leaderboard;
+pub mod market;
+pub mod pool;
+pub mod round;
 pub mod token;
 
 pub use common::*;
 pub use core::*;
+pub use leaderboard::*;
+pub use market::*;
+pub use pool::*;
+pub use round::*;
 pub use token::*;
@@ -0,0 +1,180 @@
+use solana_program::msg;
+use solana_program::program::{invoke, invoke_signed};
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+use spl_token_2022::extension::StateWithExtensions;
+
+use crate::accounts;
+use crate::error::BlinkError;
+use crate::utils::transfer_tokens;
+
+// entry fees for a round are escrowed in the pool PDA's $BASH account until the round is
+// finalized and the winners are paid
+pub fn check_pool<'a>(
+    program_id: &Pubkey,
+    game: u8,
+    date: u32,
+    pool: &'a AccountInfo<'a>,
+    pool_token: &'a AccountInfo<'a>,
+    bash_mint: &'a AccountInfo<'a>,
+    token_2022: &'a AccountInfo<'a>,
+) -> Result<u8, ProgramError> {
+    let pool_bump_seed = accounts::check_program_data_account(
+        pool,
+        program_id,
+        vec![&game.to_le_bytes(), &date.to_le_bytes(), b"Pool"],
+    )?;
+
+    accounts::check_token_account(pool, bash_mint, pool_token, token_2022)?;
+
+    Ok(pool_bump_seed)
+}
+
+fn get_decimals(mint: &AccountInfo) -> Result<u8, ProgramError> {
+    let mint_data = mint.data.borrow();
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    Ok(mint.base.decimals)
+}
+
+// moves tokens out of the pool, signed by the pool PDA
+#[allow(clippy::too_many_arguments)]
+pub fn pay_from_pool<'a>(
+    amount: u64,
+    game: u8,
+    date: u32,
+    pool: &AccountInfo<'a>,
+    pool_token: &AccountInfo<'a>,
+    bash_mint: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    token_2022: &AccountInfo<'a>,
+    pool_bump_seed: u8,
+) -> ProgramResult {
+    let decimals = get_decimals(bash_mint)?;
+
+    transfer_tokens(
+        true,
+        amount,
+        pool_token,
+        bash_mint,
+        destination,
+        pool,
+        token_2022,
+        pool_bump_seed,
+        &vec![&game.to_le_bytes(), &date.to_le_bytes(), b"Pool"],
+        decimals,
+        &Vec::new(),
+    )
+}
+
+// closes the pool's token account once it is empty, signed by the pool PDA
+pub fn close_pool<'a>(
+    game: u8,
+    date: u32,
+    pool: &AccountInfo<'a>,
+    pool_token: &AccountInfo<'a>,
+    destination: &AccountInfo<'a>,
+    token_2022: &AccountInfo<'a>,
+    pool_bump_seed: u8,
+) -> ProgramResult {
+    let ix = spl_token_2022::instruction::close_account(
+        token_2022.key,
+        pool_token.key,
+        destination.key,
+        pool.key,
+        &[],
+    )?;
+
+    invoke_signed(
+        &ix,
+        &[
+            pool_token.clone(),
+            destination.clone(),
+            pool.clone(),
+            token_2022.clone(),
+        ],
+        &[&[
+            &game.to_le_bytes(),
+            &date.to_le_bytes(),
+            b"Pool",
+            &[pool_bump_seed],
+        ]],
+    )
+}
+
+// moves a user's entry fee into the pool, signed by the user
+pub fn pay_into_pool<'a>(
+    amount: u64,
+    user: &AccountInfo<'a>,
+    user_token: &AccountInfo<'a>,
+    pool_token: &AccountInfo<'a>,
+    bash_mint: &AccountInfo<'a>,
+    token_2022: &AccountInfo<'a>,
+) -> ProgramResult {
+    let decimals = get_decimals(bash_mint)?;
+
+    let ix = spl_token_2022::instruction::transfer_checked(
+        token_2022.key,
+        user_token.key,
+        bash_mint.key,
+        pool_token.key,
+        user.key,
+        &[],
+        amount,
+        decimals,
+    )?;
+
+    invoke(
+        &ix,
+        &[
+            user_token.clone(),
+            bash_mint.clone(),
+            pool_token.clone(),
+            user.clone(),
+            token_2022.clone(),
+        ],
+    )
+}
+
+// the pool accounts are optional on instructions that only need them for fee games
+pub fn unwrap_pool_accounts<'a>(
+    pool: Option<&'a AccountInfo<'a>>,
+    pool_token: Option<&'a AccountInfo<'a>>,
+) -> Result<(&'a AccountInfo<'a>, &'a AccountInfo<'a>), ProgramError> {
+    match (pool, pool_token) {
+        (Some(pool), Some(pool_token)) => Ok((pool, pool_token)),
+        _ => {
+            msg!("pool accounts are required for games with an entry fee");
+            Err(BlinkError::MissingAccount.into())
+        }
+    }
+}
+
+// finalizing and closing a round only need the fee accounts when there is a pool to pay out of
+pub fn unwrap_fee_accounts<'a>(
+    bash_mint: Option<&'a AccountInfo<'a>>,
+    pool: Option<&'a AccountInfo<'a>>,
+    pool_token: Option<&'a AccountInfo<'a>>,
+    fees_token: Option<&'a AccountInfo<'a>>,
+    token_2022: Option<&'a AccountInfo<'a>>,
+) -> Result<
+    (
+        &'a AccountInfo<'a>,
+        &'a AccountInfo<'a>,
+        &'a AccountInfo<'a>,
+        &'a AccountInfo<'a>,
+        &'a AccountInfo<'a>,
+    ),
+    ProgramError,
+> {
+    match (bash_mint, pool, pool_token, fees_token, token_2022) {
+        (Some(bash_mint), Some(pool), Some(pool_token), Some(fees_token), Some(token_2022)) => {
+            Ok((bash_mint, pool, pool_token, fees_token, token_2022))
+        }
+        _ => {
+            msg!("pool and fee accounts are required for games with an entry fee");
+            Err(BlinkError::MissingAccount.into())
+        }
+    }
+}
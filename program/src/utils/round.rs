@@ -0,0 +1,145 @@
+use solana_program::{entrypoint::ProgramResult, msg};
+
+use crate::error::BlinkError;
+use crate::state::Game;
+
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+// the UTC calendar day, used for limits that don't depend on the game being played
+pub fn get_day(unix_timestamp: i64) -> u32 {
+    unix_timestamp.div_euclid(SECONDS_PER_DAY) as u32
+}
+
+// rounds are numbered from the unix epoch, so a game with a one day length and no offset
+// has the same rounds as the original UTC days
+pub fn get_round(game: &Game, unix_timestamp: i64) -> u32 {
+    (unix_timestamp - game.round_offset as i64).div_euclid(game.round_length as i64) as u32
+}
+
+pub fn get_round_start(game: &Game, round: u32) -> i64 {
+    round as i64 * game.round_length as i64 + game.round_offset as i64
+}
+
+pub fn get_round_end(game: &Game, round: u32) -> i64 {
+    get_round_start(game, round) + game.round_length as i64
+}
+
+pub fn get_submission_end(game: &Game, round: u32) -> i64 {
+    get_round_start(game, round) + game.submission_length as i64
+}
+
+pub fn get_voting_start(game: &Game, round: u32) -> i64 {
+    get_round_start(game, round) + game.voting_delay as i64
+}
+
+pub fn get_voting_end(game: &Game, round: u32) -> i64 {
+    get_voting_start(game, round) + game.voting_length as i64
+}
+
+pub fn check_submission_open(game: &Game, round: u32, unix_timestamp: i64) -> ProgramResult {
+    if unix_timestamp >= get_submission_end(game, round) {
+        msg!(
+            "submissions for round {} closed at {}",
+            round,
+            get_submission_end(game, round)
+        );
+        return Err(BlinkError::SubmissionClosed.into());
+    }
+
+    Ok(())
+}
+
+pub fn check_voting_open(game: &Game, round: u32, unix_timestamp: i64) -> ProgramResult {
+    if unix_timestamp < get_voting_start(game, round)
+        || unix_timestamp >= get_voting_end(game, round)
+    {
+        msg!(
+            "voting for round {} is open from {} to {}",
+            round,
+            get_voting_start(game, round),
+            get_voting_end(game, round)
+        );
+        return Err(BlinkError::VotingNotOpen.into());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{AccountType, PrizeTable};
+
+    fn game(round_length: u32, round_offset: u32) -> Game {
+        Game {
+            account_type: AccountType::Game,
+            game: 0,
+            name: String::new(),
+            description_uri: String::new(),
+            active: 1,
+            entry_reward: None,
+            vote_reward: None,
+            entry_fee: 0,
+            rake_bps: 0,
+            start_time: 0,
+            end_time: 0,
+            round_length,
+            round_offset,
+            submission_length: round_length,
+            voting_delay: 0,
+            voting_length: round_length,
+            leaderboard_size: 10,
+            prizes: PrizeTable::Fixed(Vec::new()),
+            split_ties: 0,
+        }
+    }
+
+    #[test]
+    fn daily_rounds_match_utc_days() {
+        let daily = game(SECONDS_PER_DAY as u32, 0);
+        let timestamp = 1_717_000_000;
+
+        assert_eq!(get_round(&daily, timestamp), get_day(timestamp));
+    }
+
+    #[test]
+    fn offset_shifts_round_boundaries() {
+        // daily rounds starting at 01:00 UTC
+        let shifted = game(SECONDS_PER_DAY as u32, 3600);
+        let midnight = 100 * SECONDS_PER_DAY;
+
+        assert_eq!(get_round(&shifted, midnight), 99);
+        assert_eq!(get_round(&shifted, midnight + 3599), 99);
+        assert_eq!(get_round(&shifted, midnight + 3600), 100);
+        assert_eq!(get_round_start(&shifted, 100), midnight + 3600);
+        assert_eq!(get_round_end(&shifted, 99), midnight + 3600);
+    }
+
+    #[test]
+    fn hourly_rounds() {
+        let hourly = game(3600, 0);
+
+        assert_eq!(get_round(&hourly, 7200), 2);
+        assert_eq!(get_round(&hourly, 10799), 2);
+        assert_eq!(get_round_end(&hourly, 2), 10800);
+    }
+
+    #[test]
+    fn voting_runs_on_after_submissions_close() {
+        // submit in the first 12 hours, vote from 6 hours in until the end of the next day
+        let mut phased = game(SECONDS_PER_DAY as u32, 0);
+        phased.submission_length = 12 * 3600;
+        phased.voting_delay = 6 * 3600;
+        phased.voting_length = 42 * 3600;
+        let start = get_round_start(&phased, 10);
+
+        assert!(check_submission_open(&phased, 10, start).is_ok());
+        assert!(check_submission_open(&phased, 10, start + 12 * 3600).is_err());
+
+        assert!(check_voting_open(&phased, 10, start + 6 * 3600 - 1).is_err());
+        assert!(check_voting_open(&phased, 10, start + 6 * 3600).is_ok());
+        assert!(check_voting_open(&phased, 10, start + 47 * 3600).is_ok());
+        assert!(check_voting_open(&phased, 10, start + 48 * 3600).is_err());
+        assert_eq!(get_voting_end(&phased, 10), start + 48 * 3600);
+    }
+}